@@ -0,0 +1,126 @@
+// std
+use std::str::FromStr;
+
+// Internal
+use super::Colour;
+use crate::prelude::*;
+
+impl Colour {
+    /// Returns the colour components between zero and one
+    pub fn to_unit(&self) -> (f64, f64, f64) {
+        (
+            self.r as f64 / 255f64,
+            self.g as f64 / 255f64,
+            self.b as f64 / 255f64,
+        )
+    }
+
+    /// Parses a hex colour without the '#'. Both the short (fff) and the long (ffffff) form are allowed
+    fn from_hex(hex: &str) -> Option<Self> {
+        let digits = hex
+            .chars()
+            .map(|el| el.to_digit(16).map(|t| t as u8))
+            .collect::<Option<Vec<u8>>>()?;
+
+        match digits.len() {
+            3 => Some(Self::new(digits[0] * 17, digits[1] * 17, digits[2] * 17)),
+            6 => Some(Self::new(
+                digits[0] * 16 + digits[1],
+                digits[2] * 16 + digits[3],
+                digits[4] * 16 + digits[5],
+            )),
+            _ => None,
+        }
+    }
+
+    /// Parses the inside of rgb(...). The components can be integers or percentages
+    fn from_rgb(rgb: &str) -> Option<Self> {
+        let components = rgb
+            .split(',')
+            .map(|el| {
+                let el = el.trim();
+                match el.strip_suffix('%') {
+                    Some(t) => t
+                        .parse::<f64>()
+                        .ok()
+                        .map(|t| (t.clamp(0f64, 100f64) * 2.55f64).round() as u8),
+                    None => el.parse::<f64>().ok().map(|t| t.clamp(0f64, 255f64) as u8),
+                }
+            })
+            .collect::<Option<Vec<u8>>>()?;
+
+        match components.len() {
+            3 => Some(Self::new(components[0], components[1], components[2])),
+            _ => None,
+        }
+    }
+
+    /// Returns a few common svg colour names
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "black" => Self::new(0, 0, 0),
+            "white" => Self::new(255, 255, 255),
+            "red" => Self::new(255, 0, 0),
+            "lime" => Self::new(0, 255, 0),
+            "green" => Self::new(0, 128, 0),
+            "blue" => Self::new(0, 0, 255),
+            "yellow" => Self::new(255, 255, 0),
+            "cyan" | "aqua" => Self::new(0, 255, 255),
+            "magenta" | "fuchsia" => Self::new(255, 0, 255),
+            "grey" | "gray" => Self::new(128, 128, 128),
+            "silver" => Self::new(192, 192, 192),
+            "orange" => Self::new(255, 165, 0),
+            "purple" => Self::new(128, 0, 128),
+            _ => return None,
+        })
+    }
+}
+
+impl FromStr for Colour {
+    type Err = Error;
+
+    /// Parses hex notation (#fff or #ffffff), rgb notation (rgb(255, 255, 255)) and a few common colour names
+    fn from_str(s: &str) -> Result<Self> {
+        let trimmed = s.trim().to_lowercase();
+
+        let colour = if let Some(t) = trimmed.strip_prefix('#') {
+            Self::from_hex(t)
+        } else if let Some(t) = trimmed
+            .strip_prefix("rgb(")
+            .and_then(|t| t.strip_suffix(')'))
+        {
+            Self::from_rgb(t)
+        } else {
+            Self::from_name(&trimmed)
+        };
+
+        match colour {
+            Some(t) => Ok(t),
+            None => Err(Error::Generic(format!(
+                "Can't use the colour '{s}'. Use hex notation (#ffffff), rgb notation (rgb(255, 255, 255)) or a basic colour name."
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("#ff0000".parse::<Colour>().unwrap(), Colour::new(255, 0, 0));
+        assert_eq!("#0F0".parse::<Colour>().unwrap(), Colour::new(0, 255, 0));
+        assert_eq!(
+            "rgb(10, 20, 100%)".parse::<Colour>().unwrap(),
+            Colour::new(10, 20, 255)
+        );
+        assert_eq!(
+            "Magenta".parse::<Colour>().unwrap(),
+            Colour::new(255, 0, 255)
+        );
+        assert!("#ff00".parse::<Colour>().is_err());
+        assert!("rgb(1, 2)".parse::<Colour>().is_err());
+        assert!("hsl(0, 0%, 0%)".parse::<Colour>().is_err());
+    }
+}
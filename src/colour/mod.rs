@@ -0,0 +1,16 @@
+// Modules
+pub mod functions;
+
+/// An rgb colour as used in the settings
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Colour {
+    pub fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
@@ -5,6 +5,7 @@ pub fn get_command() -> Command {
     Command::new("music_box_converter")
         .subcommand(crate::music_box_convert::MusicBoxConvert::get_command())
         .subcommand(crate::music_box_config::MusicBoxConfig::get_command())
+        .subcommand(crate::music_box_decode::MusicBoxDecode::get_command())
 }
 
 /// The Help template used by all sub applications
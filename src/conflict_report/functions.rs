@@ -0,0 +1,119 @@
+// std
+use std::{fs::File, io::Write};
+
+// Internal
+use super::ConflictReport;
+use crate::prelude::*;
+
+impl ConflictReport {
+    /// Returns the report as a table with one conflict per line
+    pub fn print(&self) -> String {
+        let mut output = match &self.title {
+            Some(t) => format!("Conflicts of '{t}' on the music box '{}'\n", self.music_box),
+            None => format!("Conflicts on the music box '{}'\n", self.music_box),
+        };
+        output += &format!(
+            "The music box needs {}mm between two notes on the same tooth. The strip moves {:.4}mm per tick.\n\n",
+            self.min_note_distance_mm, self.mm_per_tick
+        );
+
+        if self.conflicts.is_empty() {
+            output += "No notes on the same tooth are too close.\n";
+            return output;
+        }
+
+        output += &format!(
+            "{:<6}{:>22}{:>22}{:>11}{:>6}{:>6}\n",
+            "Note", "First", "Second", "Distance", "Bar", "Page"
+        );
+        for conflict in self.conflicts.iter() {
+            output += &format!(
+                "{:<6}{:>22}{:>22}{:>11}{:>6}{:>6}\n",
+                conflict.note.to_string(),
+                format!("{:.3}s ({})", conflict.first_seconds, conflict.first_tick),
+                format!("{:.3}s ({})", conflict.second_seconds, conflict.second_tick),
+                format!("{:.2}mm", conflict.distance_mm),
+                conflict.bar.map_or("-".to_string(), |t| t.to_string()),
+                conflict.page,
+            );
+        }
+        output += &format!(
+            "\n{} note(s) are too close to the previous note on the same tooth.\n",
+            self.conflicts.len()
+        );
+        output
+    }
+
+    /// Returns the report as json
+    pub fn print_json(&self) -> Result<String> {
+        match serde_json::to_string_pretty(self) {
+            Ok(t) => Ok(t),
+            Err(e) => Err(Error::SerdeJsonError(Box::new(e))),
+        }
+    }
+
+    /// Saves the report as text or as json
+    pub fn save(&self, path: &std::path::Path, json: bool) -> Result<()> {
+        let content = match json {
+            true => self.print_json()?,
+            false => self.print(),
+        };
+
+        let mut file = match File::create(path) {
+            Ok(t) => t,
+            Err(e) => {
+                return Err(Error::IOError(
+                    Box::new(e),
+                    Box::new(path.to_string_lossy().to_string()),
+                ))
+            }
+        };
+
+        match file.write_all(content.as_bytes()) {
+            Ok(t) => (),
+            Err(e) => {
+                return Err(Error::IOError(
+                    Box::new(e),
+                    Box::new(path.to_string_lossy().to_string()),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{Conflict, ConflictReport};
+    use crate::music::note::Note;
+
+    #[test]
+    fn print() {
+        let report = ConflictReport {
+            title: Some("Test".to_string()),
+            music_box: "30-note".to_string(),
+            min_note_distance_mm: 6.65f64,
+            mm_per_tick: 0.01f64,
+            conflicts: vec![Conflict {
+                note: "C4".parse::<Note>().unwrap(),
+                first_tick: 480,
+                second_tick: 720,
+                first_seconds: 0.5f64,
+                second_seconds: 0.75f64,
+                distance_mm: 2.4f64,
+                bar: Some(1),
+                page: 1,
+            }],
+        };
+
+        assert_eq!(
+            report.print().lines().nth(4).unwrap(),
+            "C4              0.500s (480)          0.750s (720)     2.40mm     1     1"
+        );
+        let json =
+            serde_json::from_str::<serde_json::Value>(&report.print_json().unwrap()).unwrap();
+        assert_eq!(json["conflicts"][0]["note"], "C4");
+        assert_eq!(json["conflicts"][0]["bar"], 1);
+    }
+}
@@ -0,0 +1,45 @@
+// Modules
+pub mod functions;
+
+// serde_derive
+use serde_derive::Serialize;
+
+// Internal
+use crate::music::note::Note;
+
+/// Every pair of notes on the same tooth that are closer than the music box can play them at the scale of the strip.
+/// Printed as text for reading or as json for other programs.
+#[derive(Debug, Serialize)]
+pub struct ConflictReport {
+    /// The title of the piece if there is one
+    pub title: Option<String>,
+    /// The name of the music box
+    pub music_box: String,
+    /// The minimum distance the music box can play two notes on the same tooth back to back
+    pub min_note_distance_mm: f64,
+    /// How far the strip moves per tempo-adjusted tick
+    pub mm_per_tick: f64,
+    /// The conflicts in the order of their second note
+    pub conflicts: Vec<Conflict>,
+}
+
+/// Two notes on the same tooth that are too close
+#[derive(Debug, Clone, Serialize)]
+pub struct Conflict {
+    /// The note of the tooth
+    pub note: Note,
+    /// The tempo-adjusted tick of the first note
+    pub first_tick: u64,
+    /// The tempo-adjusted tick of the second note
+    pub second_tick: u64,
+    /// The time of the first note in seconds from the start of the midi file
+    pub first_seconds: f64,
+    /// The time of the second note in seconds from the start of the midi file
+    pub second_seconds: f64,
+    /// The distance of the two notes on the strip
+    pub distance_mm: f64,
+    /// The bar the second note is in. `None` for SMPTE timecode
+    pub bar: Option<u32>,
+    /// The page the second note is on. The first page is page 1
+    pub page: usize,
+}
@@ -0,0 +1,314 @@
+// std
+use std::{fs::File, io::Write};
+
+// Internal
+use super::DxfWriter;
+use crate::{
+    prelude::*,
+    svg_writer::{
+        document::{Document, Layer},
+        path::PathCommand,
+        renderer::Renderer,
+        text::TextAnchor,
+    },
+    vec2::Vec2,
+};
+
+/// Two points closer than this are treated as the same point when joining the strip edges
+const EPSILON_MM: f64 = 1e-6;
+
+/// The layers with their colour index
+const LAYERS: [(&str, u8); 3] = [("HOLES", 1), ("CUT", 5), ("ENGRAVE", 3)];
+
+impl DxfWriter {
+    /// Renders a document. Set `engrave` to add the staff lines on the ENGRAVE layer
+    pub fn from_document(document: &Document, engrave: bool) -> Result<Self> {
+        let mut writer = Self {
+            height: document.size().y,
+            engrave,
+            layer: Layer::Decoration,
+            entities: String::new(),
+            cut: Vec::new(),
+        };
+        document.render(&mut writer)?;
+        Ok(writer)
+    }
+
+    /// Returns the dxf file
+    pub fn print(&self) -> String {
+        let mut out = String::new();
+
+        // Header. R12 has no unit variable, the coordinates are millimetres
+        out += "0\nSECTION\n2\nHEADER\n9\n$ACADVER\n1\nAC1009\n0\nENDSEC\n";
+
+        // Layers
+        out += &format!(
+            "0\nSECTION\n2\nTABLES\n0\nTABLE\n2\nLAYER\n70\n{}\n",
+            LAYERS.len()
+        );
+        for (name, colour) in LAYERS {
+            out += &format!("0\nLAYER\n2\n{name}\n70\n0\n62\n{colour}\n6\nCONTINUOUS\n");
+        }
+        out += "0\nENDTAB\n0\nENDSEC\n";
+
+        // Entities
+        out += "0\nSECTION\n2\nENTITIES\n";
+        out += &self.entities;
+        for polyline in join_segments(&self.cut) {
+            let closed = polyline.len() > 2
+                && distance(polyline.first().unwrap(), polyline.last().unwrap()) < EPSILON_MM;
+            let vertices = match closed {
+                true => &polyline[..polyline.len() - 1],
+                false => &polyline[..],
+            };
+
+            out += &format!(
+                "0\nPOLYLINE\n8\nCUT\n66\n1\n70\n{}\n10\n0\n20\n0\n30\n0\n",
+                closed as u8
+            );
+            for vertex in vertices {
+                out += &format!(
+                    "0\nVERTEX\n8\nCUT\n10\n{0}\n20\n{1}\n30\n0\n",
+                    vertex.x, vertex.y
+                );
+            }
+            out += "0\nSEQEND\n8\nCUT\n";
+        }
+        out += "0\nENDSEC\n0\nEOF\n";
+
+        out
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> Result<()> {
+        let mut file = match File::create(path) {
+            Ok(t) => t,
+            Err(e) => {
+                return Err(Error::IOError(
+                    Box::new(e),
+                    Box::new(path.to_string_lossy().to_string()),
+                ))
+            }
+        };
+
+        match file.write_all(self.print().as_bytes()) {
+            Ok(t) => (),
+            Err(e) => {
+                return Err(Error::IOError(
+                    Box::new(e),
+                    Box::new(path.to_string_lossy().to_string()),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Flips a point from svg coordinates (y downwards) to dxf coordinates (y upwards)
+    fn flip(&self, point: Vec2<f64>) -> Vec2<f64> {
+        Vec2::new(point.x, self.height - point.y)
+    }
+}
+
+impl Renderer for DxfWriter {
+    fn line(
+        &mut self,
+        start: Vec2<f64>,
+        end: Vec2<f64>,
+        stroke: &str,
+        stroke_width: f64,
+    ) -> Result<()> {
+        let (start, end) = (self.flip(start), self.flip(end));
+        match self.layer {
+            Layer::Cut => self.cut.push((start, end)),
+            Layer::Staff if self.engrave => {
+                self.entities += &format!(
+                    "0\nLINE\n8\nENGRAVE\n10\n{0}\n20\n{1}\n30\n0\n11\n{2}\n21\n{3}\n31\n0\n",
+                    start.x, start.y, end.x, end.y
+                )
+            }
+            _ => (),
+        }
+        Ok(())
+    }
+
+    fn circle(&mut self, centre: Vec2<f64>, radius: f64, fill: &str) -> Result<()> {
+        let layer = match self.layer {
+            Layer::Holes | Layer::Sprockets => "HOLES",
+            _ => return Ok(()),
+        };
+        let centre = self.flip(centre);
+        self.entities += &format!(
+            "0\nCIRCLE\n8\n{layer}\n10\n{0}\n20\n{1}\n30\n0\n40\n{radius}\n",
+            centre.x, centre.y
+        );
+        Ok(())
+    }
+
+    /// Text isn't written. Only the staff lines are engraved
+    fn text(
+        &mut self,
+        position: Vec2<f64>,
+        text: &str,
+        font_size: f64,
+        fill: &str,
+        anchor: TextAnchor,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// The outline of a path is written like lines. The fill is ignored
+    fn path(
+        &mut self,
+        commands: &[PathCommand<f64>],
+        fill: Option<&str>,
+        stroke: Option<&str>,
+        stroke_width: f64,
+    ) -> Result<()> {
+        let mut start = Vec2::new(0f64, 0f64);
+        let mut current = Vec2::new(0f64, 0f64);
+
+        for command in commands {
+            match command {
+                PathCommand::MoveTo(t) => {
+                    start = t.clone();
+                    current = t.clone();
+                }
+                PathCommand::LineTo(t) => {
+                    self.line(current, t.clone(), stroke.unwrap_or_default(), stroke_width)?;
+                    current = t.clone();
+                }
+                PathCommand::Close => {
+                    self.line(
+                        current,
+                        start.clone(),
+                        stroke.unwrap_or_default(),
+                        stroke_width,
+                    )?;
+                    current = start.clone();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn set_layer(&mut self, layer: Layer) {
+        self.layer = layer;
+    }
+}
+
+fn distance(a: &Vec2<f64>, b: &Vec2<f64>) -> f64 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+}
+
+/// Joins line segments that share end points into polylines. A closed polyline ends with its first point
+fn join_segments(segments: &[(Vec2<f64>, Vec2<f64>)]) -> Vec<Vec<Vec2<f64>>> {
+    let mut remaining = segments.to_vec();
+    let mut polylines = Vec::<Vec<Vec2<f64>>>::new();
+
+    while let Some((start, end)) = remaining.pop() {
+        let mut polyline = vec![start, end];
+
+        // Extend the end of the polyline until no segment connects to it
+        while let Some(i) = remaining.iter().position(|(a, b)| {
+            distance(a, polyline.last().unwrap()) < EPSILON_MM
+                || distance(b, polyline.last().unwrap()) < EPSILON_MM
+        }) {
+            let (a, b) = remaining.swap_remove(i);
+            match distance(&a, polyline.last().unwrap()) < EPSILON_MM {
+                true => polyline.push(b),
+                false => polyline.push(a),
+            }
+        }
+
+        // Extend the start as well in case we began in the middle of an open chain
+        while let Some(i) = remaining.iter().position(|(a, b)| {
+            distance(a, &polyline[0]) < EPSILON_MM || distance(b, &polyline[0]) < EPSILON_MM
+        }) {
+            let (a, b) = remaining.swap_remove(i);
+            match distance(&a, &polyline[0]) < EPSILON_MM {
+                true => polyline.insert(0, b),
+                false => polyline.insert(0, a),
+            }
+        }
+
+        polylines.push(polyline);
+    }
+
+    polylines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg_writer::{circle::Circle, line::Line, path::Path, text::Text};
+
+    fn line(x1: f64, y1: f64, x2: f64, y2: f64) -> Box<Line<f64>> {
+        Line::new_builder()
+            .set_start(x1, y1)
+            .set_end(x2, y2)
+            .set_stroke("#000000".to_string())
+            .set_stroke_width(1f64)
+            .finish()
+    }
+
+    #[test]
+    fn print() {
+        let mut document = Document::new(100f64, 50f64);
+        document.append(Layer::Staff, line(10f64, 20f64, 90f64, 20f64));
+        document.append(Layer::Score, line(10f64, 15f64, 90f64, 15f64));
+        document.append(
+            Layer::Labels,
+            Text::new_builder()
+                .set_position(10f64, 5f64)
+                .set_text("page 1 of 1".to_string())
+                .set_font_size(3f64)
+                .set_fill("#000000".to_string())
+                .set_anchor(TextAnchor::Start)
+                .finish(),
+        );
+        // The strip outline as draw_page draws it, one closed path
+        document.append(
+            Layer::Cut,
+            Path::new_builder()
+                .move_to(10f64, 10f64)
+                .line_to(90f64, 10f64)
+                .line_to(90f64, 30f64)
+                .line_to(10f64, 30f64)
+                .close()
+                .set_stroke("#0000ff".to_string())
+                .set_stroke_width(0.1f64)
+                .finish(),
+        );
+        document.append(
+            Layer::Holes,
+            Circle::new_builder()
+                .set_centre(20f64, 20f64)
+                .set_radius(1f64)
+                .set_fill("#ff0000".to_string())
+                .finish(),
+        );
+
+        let out = DxfWriter::from_document(&document, false).unwrap().print();
+        assert!(out.starts_with("0\nSECTION\n2\nHEADER\n9\n$ACADVER\n1\nAC1009\n0\nENDSEC\n"));
+        assert!(out.contains("0\nCIRCLE\n8\nHOLES\n10\n20\n20\n30\n30\n0\n40\n1\n"));
+        assert!(!out.contains("ENGRAVE\n10"));
+        // One closed polyline through the four corners, flipped to y upwards
+        let vertices = [(10, 20), (10, 40), (90, 40), (90, 20)]
+            .map(|(x, y)| format!("0\nVERTEX\n8\nCUT\n10\n{x}\n20\n{y}\n30\n0\n"))
+            .concat();
+        assert_eq!(
+            out.matches(&format!(
+                "0\nPOLYLINE\n8\nCUT\n66\n1\n70\n1\n10\n0\n20\n0\n30\n0\n{vertices}0\nSEQEND\n"
+            ))
+            .count(),
+            1
+        );
+        assert_eq!(out.matches("0\nVERTEX\n").count(), 4);
+        assert!(out.ends_with("0\nEOF\n"));
+
+        let out = DxfWriter::from_document(&document, true).unwrap().print();
+        // Only the staff line is engraved
+        assert!(out.contains("0\nLINE\n8\nENGRAVE\n10\n10\n20\n30\n"));
+        assert_eq!(out.matches("\n8\nENGRAVE\n").count(), 1);
+    }
+}
@@ -0,0 +1,21 @@
+// Modules
+pub mod functions;
+
+// Internal
+use crate::{svg_writer::document::Layer, vec2::Vec2};
+
+/// Writes a [crate::svg_writer::document::Document] into an ascii dxf (R12) file for laser cutters and cnc machines.
+/// Holes are written as circles on the HOLES layer, the strip edges as polylines on the CUT layer and the staff lines optionally as lines on the ENGRAVE layer.
+#[derive(Debug)]
+pub struct DxfWriter {
+    /// The page height in millimetres. Dxf files count y upwards so we need it to flip the y axis
+    height: f64,
+    /// Whether to write the staff lines to the ENGRAVE layer
+    engrave: bool,
+    /// The layer of the child that is currently rendered
+    layer: Layer,
+    /// The entities written so far except the strip edges
+    entities: String,
+    /// The strip edges. They are joined to polylines when printing
+    cut: Vec<(Vec2<f64>, Vec2<f64>)>,
+}
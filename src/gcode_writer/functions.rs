@@ -0,0 +1,219 @@
+// std
+use std::{fs::File, io::Write};
+
+// Internal
+use super::GcodeWriter;
+use crate::{
+    prelude::*,
+    settings::Settings,
+    svg_writer::{
+        document::{Document, Layer},
+        path::PathCommand,
+        renderer::Renderer,
+        text::TextAnchor,
+    },
+    vec2::Vec2,
+};
+
+impl GcodeWriter {
+    /// Collects the holes of a document. Feed rates, heights and the origin are taken from the settings
+    pub fn from_document(document: &Document, settings: &Settings) -> Result<Self> {
+        if settings.gcode_travel_feed_mm_min <= 0f64 || settings.gcode_plunge_feed_mm_min <= 0f64 {
+            return Err(Error::Generic(
+                "The G-code feed rates have to be greater than zero. Consider changing them in the configurator.".to_string(),
+            ));
+        }
+
+        let mut writer = Self {
+            travel_feed: settings.gcode_travel_feed_mm_min,
+            plunge_feed: settings.gcode_plunge_feed_mm_min,
+            safe_z: settings.gcode_safe_z_mm,
+            punch_z: settings.gcode_punch_z_mm,
+            origin: Vec2::new(settings.gcode_origin_x_mm, settings.gcode_origin_y_mm),
+            ..Default::default()
+        };
+        document.render(&mut writer)?;
+        Ok(writer)
+    }
+
+    /// Returns the holes in the order they are punched. Always goes to the closest hole that wasn't punched yet, starting at the top left corner of the page
+    pub fn ordered_holes(&self) -> Vec<Vec2<f64>> {
+        let mut remaining = self.holes.clone();
+        let mut ordered = Vec::<Vec2<f64>>::with_capacity(remaining.len());
+        let mut current = Vec2::new(0f64, 0f64);
+
+        while !remaining.is_empty() {
+            let mut closest = 0;
+            let mut closest_distance = f64::MAX;
+            for (i, hole) in remaining.iter().enumerate() {
+                let distance = (hole.x - current.x).powi(2) + (hole.y - current.y).powi(2);
+                if distance < closest_distance {
+                    closest = i;
+                    closest_distance = distance;
+                }
+            }
+
+            current = remaining.swap_remove(closest);
+            ordered.push(current.clone());
+        }
+
+        ordered
+    }
+
+    /// Returns the G-code
+    pub fn print(&self) -> String {
+        let mut out = String::new();
+
+        out += "; Generated by music_box_converter\n";
+        out += "; Millimetres, same coordinates as the svg file (y grows downwards)\n";
+        out += "G21\nG90\n";
+        out += &format!("G0 Z{}\n", self.safe_z);
+
+        for hole in self.ordered_holes() {
+            out += &format!(
+                "G0 X{0:.3} Y{1:.3} F{2}\n",
+                hole.x + self.origin.x,
+                hole.y + self.origin.y,
+                self.travel_feed
+            );
+            out += &format!("G1 Z{0} F{1}\n", self.punch_z, self.plunge_feed);
+            out += &format!("G0 Z{0} F{1}\n", self.safe_z, self.travel_feed);
+        }
+
+        out += &format!(
+            "G0 X{0:.3} Y{1:.3} F{2}\n",
+            self.origin.x, self.origin.y, self.travel_feed
+        );
+        out += "M2\n";
+
+        out
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> Result<()> {
+        let mut file = match File::create(path) {
+            Ok(t) => t,
+            Err(e) => {
+                return Err(Error::IOError(
+                    Box::new(e),
+                    Box::new(path.to_string_lossy().to_string()),
+                ))
+            }
+        };
+
+        match file.write_all(self.print().as_bytes()) {
+            Ok(t) => (),
+            Err(e) => {
+                return Err(Error::IOError(
+                    Box::new(e),
+                    Box::new(path.to_string_lossy().to_string()),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Renderer for GcodeWriter {
+    /// Lines aren't punched
+    fn line(
+        &mut self,
+        start: Vec2<f64>,
+        end: Vec2<f64>,
+        stroke: &str,
+        stroke_width: f64,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn circle(&mut self, centre: Vec2<f64>, radius: f64, fill: &str) -> Result<()> {
+        if let Some(Layer::Holes | Layer::Sprockets) = self.layer {
+            self.holes.push(centre);
+        }
+        Ok(())
+    }
+
+    /// Text isn't punched
+    fn text(
+        &mut self,
+        position: Vec2<f64>,
+        text: &str,
+        font_size: f64,
+        fill: &str,
+        anchor: TextAnchor,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Paths aren't punched
+    fn path(
+        &mut self,
+        commands: &[PathCommand<f64>],
+        fill: Option<&str>,
+        stroke: Option<&str>,
+        stroke_width: f64,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn set_layer(&mut self, layer: Layer) {
+        self.layer = Some(layer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg_writer::circle::Circle;
+
+    fn hole(x: f64, y: f64) -> Box<Circle<f64>> {
+        Circle::new_builder()
+            .set_centre(x, y)
+            .set_radius(1f64)
+            .set_fill("#ff0000".to_string())
+            .finish()
+    }
+
+    #[test]
+    fn nearest_neighbour() {
+        let mut document = Document::default();
+        document.append(Layer::Holes, hole(50f64, 10f64));
+        document.append(Layer::Holes, hole(10f64, 10f64));
+        document.append(Layer::Sprockets, hole(12f64, 30f64));
+        document.append(Layer::Holes, hole(48f64, 30f64));
+        document.append(Layer::Decoration, hole(0f64, 0f64));
+
+        let settings = Settings {
+            gcode_travel_feed_mm_min: 3000f64,
+            gcode_plunge_feed_mm_min: 300f64,
+            gcode_safe_z_mm: 5f64,
+            gcode_punch_z_mm: -1f64,
+            gcode_origin_x_mm: 100f64,
+            ..Default::default()
+        };
+        let writer = GcodeWriter::from_document(&document, &settings).unwrap();
+
+        let order = writer
+            .ordered_holes()
+            .iter()
+            .map(|el| (el.x, el.y))
+            .collect::<Vec<(f64, f64)>>();
+        assert_eq!(
+            order,
+            vec![
+                (10f64, 10f64),
+                (12f64, 30f64),
+                (48f64, 30f64),
+                (50f64, 10f64)
+            ]
+        );
+
+        let out = writer.print();
+        assert!(out.contains("G0 X110.000 Y10.000 F3000\nG1 Z-1 F300\nG0 Z5 F3000\n"));
+        assert!(out.ends_with("G0 X100.000 Y0.000 F3000\nM2\n"));
+        assert_eq!(out.matches("G1 Z-1").count(), 4);
+
+        let settings = Settings::default();
+        assert!(GcodeWriter::from_document(&document, &settings).is_err());
+    }
+}
@@ -0,0 +1,25 @@
+// Modules
+pub mod functions;
+
+// Internal
+use crate::{svg_writer::document::Layer, vec2::Vec2};
+
+/// Writes the note and sprocket holes of a [crate::svg_writer::document::Document] as G-code for plotters and drilling or punching machines.
+/// The coordinates are the same millimetres as in the svg so y grows downwards.
+#[derive(Debug, Default)]
+pub struct GcodeWriter {
+    /// The feed rate for moving between holes in millimetres per minute
+    travel_feed: f64,
+    /// The feed rate for moving into the paper in millimetres per minute
+    plunge_feed: f64,
+    /// The height for moving between holes
+    safe_z: f64,
+    /// The height for punching a hole
+    punch_z: f64,
+    /// Added to every hole position
+    origin: Vec2<f64>,
+    /// The layer of the child that is currently rendered
+    layer: Option<Layer>,
+    /// The centres of all holes in the order they were drawn
+    holes: Vec<Vec2<f64>>,
+}
@@ -0,0 +1,354 @@
+// std
+use std::ops::Range;
+
+// Internal
+use super::{Bounds, Hole, ImageReader};
+use crate::prelude::*;
+
+/// How much of a pixel row or column has to be lighter than the threshold for it to be part of the strip
+const STRIP_FILL: f64 = 0.5f64;
+
+/// How much of its bounding box a hole has to cover. A circle covers about 0.785
+const HOLE_FILL: f64 = 0.5f64;
+
+/// How many times wider than high a hole can be and the other way around
+const HOLE_ASPECT: f64 = 2f64;
+
+impl ImageReader {
+    /// Whether the data starts like a png or a jpeg file
+    pub fn is_image(data: &[u8]) -> bool {
+        data.starts_with(b"\x89PNG") || data.starts_with(&[0xff, 0xd8])
+    }
+
+    /// Decodes a png or jpeg file into the brightness of its pixels
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        if data.starts_with(b"\x89PNG") {
+            Self::decode_png(data)
+        } else if data.starts_with(&[0xff, 0xd8]) {
+            Self::decode_jpeg(data)
+        } else {
+            Err(Error::Generic(
+                "The image is neither a png nor a jpeg file.".to_string(),
+            ))
+        }
+    }
+
+    fn decode_png(data: &[u8]) -> Result<Self> {
+        let mut decoder = png::Decoder::new(data);
+        // Expands palettes and reduces 16 bit to 8 bit
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = match decoder.read_info() {
+            Ok(t) => t,
+            Err(e) => return Err(Error::Generic(format!("Couldn't read the png file: {e}"))),
+        };
+        let mut buf = vec![0u8; reader.output_buffer_size()];
+        let info = match reader.next_frame(&mut buf) {
+            Ok(t) => t,
+            Err(e) => return Err(Error::Generic(format!("Couldn't read the png file: {e}"))),
+        };
+        Ok(Self::from_channels(
+            info.width as usize,
+            info.height as usize,
+            &buf[..info.buffer_size()],
+            info.color_type.samples(),
+        ))
+    }
+
+    fn decode_jpeg(data: &[u8]) -> Result<Self> {
+        let mut decoder = jpeg_decoder::Decoder::new(data);
+        let buf = match decoder.decode() {
+            Ok(t) => t,
+            Err(e) => return Err(Error::Generic(format!("Couldn't read the jpeg file: {e}"))),
+        };
+        let info = match decoder.info() {
+            Some(t) => t,
+            None => return Err(Error::Generic("Couldn't read the jpeg file.".to_string())),
+        };
+        let (width, height) = (info.width as usize, info.height as usize);
+        match info.pixel_format {
+            jpeg_decoder::PixelFormat::L8 => Ok(Self::from_channels(width, height, &buf, 1)),
+            jpeg_decoder::PixelFormat::RGB24 => Ok(Self::from_channels(width, height, &buf, 3)),
+            // Big endian, the high byte is enough
+            jpeg_decoder::PixelFormat::L16 => Ok(Self {
+                width,
+                height,
+                pixels: buf.iter().step_by(2).copied().collect(),
+            }),
+            jpeg_decoder::PixelFormat::CMYK32 => Err(Error::Generic(
+                "CMYK jpeg files aren't supported. Save the scan as RGB or grayscale.".to_string(),
+            )),
+        }
+    }
+
+    /// Converts 8 bit grayscale or rgb pixels into their brightness. An alpha channel is ignored
+    fn from_channels(width: usize, height: usize, data: &[u8], channels: usize) -> Self {
+        let pixels = data
+            .chunks_exact(channels)
+            .map(|el| match channels {
+                1 | 2 => el[0],
+                _ => ((299 * el[0] as u32 + 587 * el[1] as u32 + 114 * el[2] as u32) / 1000) as u8,
+            })
+            .collect();
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// The brightness that seperates the strip from the background and the holes. Uses Otsu's method
+    pub fn threshold(&self) -> u8 {
+        let mut histogram = [0u64; 256];
+        for el in self.pixels.iter() {
+            histogram[*el as usize] += 1;
+        }
+        let total = self.pixels.len() as f64;
+        let sum_all = histogram
+            .iter()
+            .enumerate()
+            .map(|(i, el)| i as f64 * *el as f64)
+            .sum::<f64>();
+
+        let (mut weight, mut sum) = (0f64, 0f64);
+        let (mut best, mut best_variance) = (0u8, -1f64);
+        for (i, count) in histogram.iter().enumerate() {
+            weight += *count as f64;
+            sum += i as f64 * *count as f64;
+            let rest = total - weight;
+            if weight == 0f64 || rest == 0f64 {
+                continue;
+            }
+            // The variance between the dark and the light pixels
+            let variance = weight * rest * (sum / weight - (sum_all - sum) / rest).powi(2);
+            if variance > best_variance {
+                best_variance = variance;
+                best = i as u8;
+            }
+        }
+        best
+    }
+
+    /// Finds the strip as the longest run of mostly light pixel rows and the longest run of mostly light columns within them.
+    /// Dark runs of up to max_gap pixels, like printed staff lines, don't end the strip
+    pub fn bounds(&self, threshold: u8, max_gap: usize) -> Option<Bounds> {
+        let (top, bottom) = Self::longest_run(
+            (0..self.height).map(|y| self.light_share(0..self.width, y..y + 1, threshold)),
+            max_gap,
+        )?;
+        let (left, right) = Self::longest_run(
+            (0..self.width).map(|x| self.light_share(x..x + 1, top..bottom, threshold)),
+            max_gap,
+        )?;
+        Some(Bounds {
+            left,
+            top,
+            right,
+            bottom,
+        })
+    }
+
+    /// How much of the area is lighter than the threshold
+    fn light_share(&self, xs: Range<usize>, ys: Range<usize>, threshold: u8) -> f64 {
+        let area = xs.len() * ys.len();
+        let light = ys
+            .flat_map(|y| xs.clone().map(move |x| (x, y)))
+            .filter(|(x, y)| self.pixels[y * self.width + x] > threshold)
+            .count();
+        light as f64 / area as f64
+    }
+
+    /// Returns the start and the exclusive end of the longest run of shares above STRIP_FILL. Gaps of up to max_gap shares are bridged
+    fn longest_run(shares: impl Iterator<Item = f64>, max_gap: usize) -> Option<(usize, usize)> {
+        let mut best = None::<(usize, usize)>;
+        let mut start = None::<usize>;
+        // The exclusive end of the last share above STRIP_FILL
+        let mut end = 0usize;
+        // The zeros at the end close a run that reaches the edge
+        let padding = std::iter::repeat_n(0f64, max_gap + 1);
+        for (i, share) in shares.chain(padding).enumerate() {
+            if share > STRIP_FILL {
+                start.get_or_insert(i);
+                end = i + 1;
+                continue;
+            }
+            if let Some(t) = start {
+                if i - end >= max_gap {
+                    if best.is_none_or(|el| end - t > el.1 - el.0) {
+                        best = Some((t, end));
+                    }
+                    start = None;
+                }
+            }
+        }
+        best
+    }
+
+    /// Finds the round spots darker than the threshold inside the bounds with a diameter between min_diameter and max_diameter pixels.
+    /// Lines thinner than half the min_diameter, like printed staff lines, are removed first. Spots touching the edge of the bounds are left out
+    pub fn holes(
+        &self,
+        bounds: &Bounds,
+        threshold: u8,
+        min_diameter: f64,
+        max_diameter: f64,
+    ) -> Vec<Hole> {
+        let width = bounds.right - bounds.left;
+        let height = bounds.bottom - bounds.top;
+        let mut dark = vec![false; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                dark[y * width + x] =
+                    self.pixels[(y + bounds.top) * self.width + x + bounds.left] <= threshold;
+            }
+        }
+
+        // Shrinking the dark areas removes thin lines, growing them again restores the holes
+        let radius = (min_diameter / 4f64).floor() as usize;
+        let dark = Self::morph(
+            &Self::morph(&dark, width, height, radius, true),
+            width,
+            height,
+            radius,
+            false,
+        );
+
+        let mut holes = Vec::<Hole>::new();
+        let mut seen = vec![false; width * height];
+        let mut stack = Vec::<usize>::new();
+        for start in 0..width * height {
+            if !dark[start] || seen[start] {
+                continue;
+            }
+
+            // Flood fill the spot
+            seen[start] = true;
+            stack.push(start);
+            let (mut min_x, mut min_y, mut max_x, mut max_y) = (usize::MAX, usize::MAX, 0, 0);
+            let (mut count, mut sum_x, mut sum_y) = (0usize, 0f64, 0f64);
+            while let Some(i) = stack.pop() {
+                let (x, y) = (i % width, i / width);
+                min_x = min_x.min(x);
+                max_x = max_x.max(x);
+                min_y = min_y.min(y);
+                max_y = max_y.max(y);
+                count += 1;
+                sum_x += x as f64;
+                sum_y += y as f64;
+
+                let neighbours = [
+                    (x > 0).then(|| i - 1),
+                    (x + 1 < width).then(|| i + 1),
+                    (y > 0).then(|| i - width),
+                    (y + 1 < height).then(|| i + width),
+                ];
+                for t in neighbours.into_iter().flatten() {
+                    if dark[t] && !seen[t] {
+                        seen[t] = true;
+                        stack.push(t);
+                    }
+                }
+            }
+
+            let spot_width = (max_x - min_x + 1) as f64;
+            let spot_height = (max_y - min_y + 1) as f64;
+            let diameter = spot_width.max(spot_height);
+            if min_x == 0
+                || min_y == 0
+                || max_x + 1 == width
+                || max_y + 1 == height
+                || diameter < min_diameter
+                || diameter > max_diameter
+                || diameter / spot_width.min(spot_height) > HOLE_ASPECT
+                || (count as f64) < spot_width * spot_height * HOLE_FILL
+            {
+                continue;
+            }
+
+            // The centre of a pixel is half a pixel from its edge
+            holes.push(Hole {
+                x: bounds.left as f64 + sum_x / count as f64 + 0.5f64,
+                y: bounds.top as f64 + sum_y / count as f64 + 0.5f64,
+                diameter,
+            });
+        }
+
+        holes
+    }
+
+    /// Shrinks (all) or grows the set pixels of the mask by a square with the given radius
+    fn morph(mask: &[bool], width: usize, height: usize, radius: usize, all: bool) -> Vec<bool> {
+        if radius == 0 {
+            return mask.to_vec();
+        }
+        let mut rows = vec![false; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                let xs = x.saturating_sub(radius)..(x + radius + 1).min(width);
+                rows[y * width + x] = Self::window(xs.map(|t| mask[y * width + t]), all);
+            }
+        }
+        let mut output = vec![false; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                let ys = y.saturating_sub(radius)..(y + radius + 1).min(height);
+                output[y * width + x] = Self::window(ys.map(|t| rows[t * width + x]), all);
+            }
+        }
+        output
+    }
+
+    /// Whether all or any of the values are set
+    fn window(mut values: impl Iterator<Item = bool>, all: bool) -> bool {
+        match all {
+            true => values.all(|el| el),
+            false => values.any(|el| el),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn holes() {
+        // A light strip on a dark background with a staff line and a hole on it
+        let (width, height) = (40usize, 24usize);
+        let mut pixels = vec![20u8; width * height];
+        for y in 4..20 {
+            for x in 2..38 {
+                let (dx, dy) = (x as f64 + 0.5f64 - 20f64, y as f64 + 0.5f64 - 12f64);
+                pixels[y * width + x] = match (y == 12, dx * dx + dy * dy <= 9f64) {
+                    (_, true) => 20,
+                    (true, false) => 90,
+                    _ => 230,
+                };
+            }
+        }
+        let image = ImageReader {
+            width,
+            height,
+            pixels,
+        };
+
+        let threshold = image.threshold();
+        let bounds = image.bounds(threshold, 2).unwrap();
+        assert_eq!(
+            bounds,
+            Bounds {
+                left: 2,
+                top: 4,
+                right: 38,
+                bottom: 20
+            }
+        );
+        assert_eq!(
+            image.holes(&bounds, threshold, 4f64, 10f64),
+            vec![Hole {
+                x: 20f64,
+                y: 12f64,
+                diameter: 6f64
+            }]
+        );
+    }
+}
@@ -0,0 +1,33 @@
+// Modules
+pub mod functions;
+
+/// Finds the strip and its punched holes in a scanned or photographed image. Expects a light strip lying horizontally on a darker background
+#[derive(Debug, Default)]
+pub struct ImageReader {
+    /// The width in pixels
+    width: usize,
+    /// The height in pixels
+    height: usize,
+    /// The brightness of every pixel row by row
+    pixels: Vec<u8>,
+}
+
+/// The part of the image covered by the strip. In pixels, the end is exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub left: usize,
+    pub top: usize,
+    pub right: usize,
+    pub bottom: usize,
+}
+
+/// A hole found in the strip. In pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hole {
+    /// The horizontal position of the centre
+    pub x: f64,
+    /// The vertical position of the centre
+    pub y: f64,
+    /// The larger side of the bounding box
+    pub diameter: f64,
+}
@@ -66,7 +66,10 @@ mod tests {
     fn lang() {
         let lang = LangMap::load_from_fs("fallback"); // Not a real path, so it uses the fallback hashmap
 
-        assert_eq!(lang.get_key_value("MusicBox").unwrap().1, "music box");
+        assert_eq!(
+            lang.get_key_value("capital.title").unwrap().1,
+            "Music box configurator"
+        );
     }
 
     #[test]
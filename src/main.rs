@@ -1,21 +1,32 @@
 #![allow(unused)]
 // Modules
+pub mod colour;
 pub mod command;
+pub mod conflict_report;
+pub mod dxf_writer;
 pub mod error;
+pub mod gcode_writer;
+pub mod image_reader;
 pub mod lang;
 pub mod music;
 pub mod music_box_config;
 pub mod music_box_convert;
+pub mod music_box_decode;
 pub mod path;
+pub mod pdf_writer;
+pub mod png_writer;
 pub mod prelude;
 pub mod settings;
+pub mod svg_reader;
 pub mod svg_writer;
 pub mod vec2;
+pub mod wav_writer;
 
 // Internal
 use crate::command::get_command;
 use crate::music_box_config::MusicBoxConfig;
 use crate::music_box_convert::MusicBoxConvert;
+use crate::music_box_decode::MusicBoxDecode;
 use crate::prelude::*;
 
 fn main() -> Result<()> {
@@ -23,6 +34,7 @@ fn main() -> Result<()> {
     let result = match args.subcommand() {
         Some(("convert", sub_m)) => music_box_convert(sub_m),
         Some(("config", sub_m)) => music_box_config(sub_m),
+        Some(("decode", sub_m)) => music_box_decode(sub_m),
         _ => match get_command().print_help() {
             Ok(t) => Ok(t),
             Err(e) => Err(Error::IOError(
@@ -50,9 +62,19 @@ fn music_box_convert(args: &clap::ArgMatches) -> Result<()> {
     converter.run_output_file()
 }
 
+fn music_box_decode(args: &clap::ArgMatches) -> Result<()> {
+    let decoder = MusicBoxDecode::new(args);
+    decoder.run()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{lang::LangMap, music_box_convert::MusicBoxConvert};
+    use crate::{
+        lang::LangMap,
+        music::{music_box::MusicBox, tempo_map::TempoMap, track::Track},
+        music_box_convert::MusicBoxConvert,
+        music_box_decode::MusicBoxDecode,
+    };
 
     fn run(args: clap::ArgMatches) -> Vec<String> {
         match match args.subcommand() {
@@ -69,6 +91,15 @@ mod tests {
         converter.run_output_string()
     }
 
+    /// Decodes a png file into its size and rgb pixels
+    fn decode_png(png: &[u8]) -> (u32, u32, Vec<u8>) {
+        let mut reader = png::Decoder::new(png).read_info().unwrap();
+        let mut pixels = vec![0u8; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        pixels.truncate(info.buffer_size());
+        (info.width, info.height, pixels)
+    }
+
     #[test]
     fn test_default() {
         let args = [
@@ -86,195 +117,457 @@ mod tests {
 
         assert_eq!(
             res[0],
-            r##"<svg version="1.1" xmlns="http://www.w3.org/2000/svg">
-<line x1="10mm" y1="10mm" x2="306mm" y2="10mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="12.006551724137932mm" x2="306mm" y2="12.006551724137932mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="14.013103448275862mm" x2="306mm" y2="14.013103448275862mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="16.019655172413792mm" x2="306mm" y2="16.019655172413792mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="18.026206896551724mm" x2="306mm" y2="18.026206896551724mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="20.032758620689656mm" x2="306mm" y2="20.032758620689656mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="22.039310344827584mm" x2="306mm" y2="22.039310344827584mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="24.045862068965516mm" x2="306mm" y2="24.045862068965516mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="26.052413793103447mm" x2="306mm" y2="26.052413793103447mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="28.05896551724138mm" x2="306mm" y2="28.05896551724138mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="30.06551724137931mm" x2="306mm" y2="30.06551724137931mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="32.07206896551724mm" x2="306mm" y2="32.07206896551724mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="34.07862068965517mm" x2="306mm" y2="34.07862068965517mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="36.0851724137931mm" x2="306mm" y2="36.0851724137931mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="38.09172413793103mm" x2="306mm" y2="38.09172413793103mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="40.09827586206896mm" x2="306mm" y2="40.09827586206896mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="42.104827586206895mm" x2="306mm" y2="42.104827586206895mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="44.11137931034482mm" x2="306mm" y2="44.11137931034482mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="46.11793103448276mm" x2="306mm" y2="46.11793103448276mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="48.12448275862069mm" x2="306mm" y2="48.12448275862069mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="50.13103448275862mm" x2="306mm" y2="50.13103448275862mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="52.13758620689655mm" x2="306mm" y2="52.13758620689655mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="54.14413793103448mm" x2="306mm" y2="54.14413793103448mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="56.150689655172414mm" x2="306mm" y2="56.150689655172414mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="58.15724137931034mm" x2="306mm" y2="58.15724137931034mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="60.16379310344827mm" x2="306mm" y2="60.16379310344827mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="62.170344827586206mm" x2="306mm" y2="62.170344827586206mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="64.17689655172413mm" x2="306mm" y2="64.17689655172413mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="66.18344827586206mm" x2="306mm" y2="66.18344827586206mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="68.19mm" x2="306mm" y2="68.19mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="5mm" x2="10mm" y2="73.19mm" stroke="#ff00ff" stroke_width="1mm" />
-<line x1="306mm" y1="5mm" x2="306mm" y2="73.19mm" stroke="#ff00ff" stroke_width="1mm" />
-<line x1="10mm" y1="5mm" x2="306mm" y2="5mm" stroke="#00ff00" stroke_width="1mm" />
-<line x1="10mm" y1="73.19mm" x2="306mm" y2="73.19mm" stroke="#00ff00" stroke_width="1mm" />
-<circle cx="10mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="10mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="10mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="10mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="18mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="26mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="26mm" cy="64.17689655172413mm" r="1mm" fill="#ff0000" />
-<circle cx="26mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="26mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="34mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="34mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="34mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="34mm" cy="52.13758620689655mm" r="1mm" fill="#ff0000" />
-<circle cx="42mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="42mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="42mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="42mm" cy="52.13758620689655mm" r="1mm" fill="#ff0000" />
-<circle cx="50mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="50mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="50mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="58mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="58mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="58mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="58mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="74mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="74mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="74mm" cy="52.13758620689655mm" r="1mm" fill="#ff0000" />
-<circle cx="82mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="86mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="90mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="98mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="102mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="106mm" cy="64.17689655172413mm" r="1mm" fill="#ff0000" />
-<circle cx="106mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="106mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="114mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="118mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="122mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="122mm" cy="64.17689655172413mm" r="1mm" fill="#ff0000" />
-<circle cx="122mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="122mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="130mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="134mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="138mm" cy="64.17689655172413mm" r="1mm" fill="#ff0000" />
-<circle cx="138mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="138mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="154mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="154mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="154mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="170mm" cy="44.11137931034482mm" r="1mm" fill="#ff0000" />
-<circle cx="170mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="170mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="170mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="178mm" cy="44.11137931034482mm" r="1mm" fill="#ff0000" />
-<circle cx="186mm" cy="44.11137931034482mm" r="1mm" fill="#ff0000" />
-<circle cx="186mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="186mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="186mm" cy="50.13103448275862mm" r="1mm" fill="#ff0000" />
-<circle cx="194mm" cy="48.12448275862069mm" r="1mm" fill="#ff0000" />
-<circle cx="202mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="202mm" cy="64.17689655172413mm" r="1mm" fill="#ff0000" />
-<circle cx="202mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="202mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="218mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="218mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="218mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="218mm" cy="50.13103448275862mm" r="1mm" fill="#ff0000" />
-<circle cx="234mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="234mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="234mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="234mm" cy="52.13758620689655mm" r="1mm" fill="#ff0000" />
-<circle cx="242mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="250mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="250mm" cy="64.17689655172413mm" r="1mm" fill="#ff0000" />
-<circle cx="250mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="250mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="258mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="266mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="266mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="266mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="266mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="298mm" cy="44.11137931034482mm" r="1mm" fill="#ff0000" />
-<circle cx="298mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="298mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="298mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="306mm" cy="44.11137931034482mm" r="1mm" fill="#ff0000" />
+            r##"<svg version="1.1" xmlns="http://www.w3.org/2000/svg" width="297mm" height="210mm" viewBox="0 0 297 210">
+<g id="staff">
+<line x1="10" y1="17" x2="289.3" y2="17" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="19.006551724137932" x2="289.3" y2="19.006551724137932" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="21.013103448275864" x2="289.3" y2="21.013103448275864" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="23.019655172413792" x2="289.3" y2="23.019655172413792" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="25.026206896551724" x2="289.3" y2="25.026206896551724" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="27.032758620689656" x2="289.3" y2="27.032758620689656" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="29.039310344827584" x2="289.3" y2="29.039310344827584" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="31.045862068965516" x2="289.3" y2="31.045862068965516" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="33.05241379310345" x2="289.3" y2="33.05241379310345" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="35.058965517241376" x2="289.3" y2="35.058965517241376" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="37.06551724137931" x2="289.3" y2="37.06551724137931" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="39.07206896551724" x2="289.3" y2="39.07206896551724" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="41.07862068965517" x2="289.3" y2="41.07862068965517" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="43.0851724137931" x2="289.3" y2="43.0851724137931" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="45.09172413793103" x2="289.3" y2="45.09172413793103" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="47.09827586206896" x2="289.3" y2="47.09827586206896" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="49.104827586206895" x2="289.3" y2="49.104827586206895" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="51.11137931034482" x2="289.3" y2="51.11137931034482" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="53.11793103448276" x2="289.3" y2="53.11793103448276" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="55.12448275862069" x2="289.3" y2="55.12448275862069" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="57.13103448275862" x2="289.3" y2="57.13103448275862" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="59.13758620689655" x2="289.3" y2="59.13758620689655" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="61.14413793103448" x2="289.3" y2="61.14413793103448" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="63.150689655172414" x2="289.3" y2="63.150689655172414" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="65.15724137931034" x2="289.3" y2="65.15724137931034" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="67.16379310344827" x2="289.3" y2="67.16379310344827" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="69.1703448275862" x2="289.3" y2="69.1703448275862" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="71.17689655172413" x2="289.3" y2="71.17689655172413" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="73.18344827586206" x2="289.3" y2="73.18344827586206" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="75.19" x2="289.3" y2="75.19" stroke="#000000" stroke-width="1" />
+</g>
+<g id="score">
+<line x1="10" y1="12" x2="10" y2="80.19" stroke="#ff00ff" stroke-width="1" />
+<line x1="289.3" y1="12" x2="289.3" y2="80.19" stroke="#ff00ff" stroke-width="1" />
+<line x1="10" y1="12" x2="289.3" y2="12" stroke="#00ff00" stroke-width="1" />
+<line x1="10" y1="80.19" x2="289.3" y2="80.19" stroke="#00ff00" stroke-width="1" />
+</g>
+<g id="decorations">
+<line x1="279.3" y1="8" x2="279.3" y2="11" stroke="#ff00ff" stroke-width="1" />
+<line x1="279.3" y1="81.19" x2="279.3" y2="84.19" stroke="#ff00ff" stroke-width="1" />
+<line x1="292.3" y1="43.095" x2="295.3" y2="46.095" stroke="#ff00ff" stroke-width="1" />
+<line x1="292.3" y1="49.095" x2="295.3" y2="46.095" stroke="#ff00ff" stroke-width="1" />
+</g>
+<g id="labels">
+<text x="293.8" y="51.595" font-size="2" font-family="sans-serif" fill="#ff00ff" text-anchor="middle">p. 2</text>
+<text x="10" y="7" font-size="3" font-family="sans-serif" fill="#000000" text-anchor="start">Piano</text>
+<text x="148.5" y="7" font-size="3" font-family="sans-serif" fill="#000000" text-anchor="middle">30-note</text>
+<text x="287" y="7" font-size="3" font-family="sans-serif" fill="#000000" text-anchor="end">page 1 of 3</text>
+</g>
+<g id="sprockets">
+<circle cx="10" cy="14.5" r="1" fill="#ffff00" />
+<circle cx="10" cy="77.69" r="1" fill="#ffff00" />
+<circle cx="60" cy="14.5" r="1" fill="#ffff00" />
+<circle cx="60" cy="77.69" r="1" fill="#ffff00" />
+<circle cx="110" cy="14.5" r="1" fill="#ffff00" />
+<circle cx="110" cy="77.69" r="1" fill="#ffff00" />
+<circle cx="160" cy="14.5" r="1" fill="#ffff00" />
+<circle cx="160" cy="77.69" r="1" fill="#ffff00" />
+<circle cx="210" cy="14.5" r="1" fill="#ffff00" />
+<circle cx="210" cy="77.69" r="1" fill="#ffff00" />
+<circle cx="260" cy="14.5" r="1" fill="#ffff00" />
+<circle cx="260" cy="77.69" r="1" fill="#ffff00" />
+</g>
+<g id="holes">
+<circle cx="10" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="10" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="10" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="10" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="10" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="23.3" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="36.6" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="36.6" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="36.6" cy="71.17689655172413" r="1" fill="#ff0000" />
+<circle cx="36.6" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="36.6" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="49.900000000000006" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="49.900000000000006" cy="59.13758620689655" r="1" fill="#ff0000" />
+<circle cx="49.900000000000006" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="49.900000000000006" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="49.900000000000006" cy="59.13758620689655" r="1" fill="#ff0000" />
+<circle cx="63.2" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="63.2" cy="73.18344827586206" r="1" fill="#ff0000" />
+<circle cx="63.2" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="63.2" cy="63.150689655172414" r="1" fill="#ff0000" />
+<circle cx="63.2" cy="59.13758620689655" r="1" fill="#ff0000" />
+<circle cx="76.5" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="76.5" cy="53.11793103448276" r="1" fill="#ff0000" />
+<circle cx="76.5" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="76.5" cy="63.150689655172414" r="1" fill="#ff0000" />
+<circle cx="76.5" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="89.80000000000001" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="89.80000000000001" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="89.80000000000001" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="89.80000000000001" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="89.80000000000001" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="116.4" cy="59.13758620689655" r="1" fill="#ff0000" />
+<circle cx="116.4" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="116.4" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="116.4" cy="59.13758620689655" r="1" fill="#ff0000" />
+<circle cx="129.7" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="136.35000000000002" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="143" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="156.3" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="162.95000000000002" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="169.60000000000002" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="169.60000000000002" cy="71.17689655172413" r="1" fill="#ff0000" />
+<circle cx="169.60000000000002" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="169.60000000000002" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="182.9" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="189.55" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="196.20000000000002" cy="63.150689655172414" r="1" fill="#ff0000" />
+<circle cx="196.20000000000002" cy="71.17689655172413" r="1" fill="#ff0000" />
+<circle cx="196.20000000000002" cy="71.17689655172413" r="1" fill="#ff0000" />
+<circle cx="196.20000000000002" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="196.20000000000002" cy="63.150689655172414" r="1" fill="#ff0000" />
+<circle cx="209.5" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="216.15" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="222.8" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="222.8" cy="71.17689655172413" r="1" fill="#ff0000" />
+<circle cx="222.8" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="222.8" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="249.4" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="249.4" cy="53.11793103448276" r="1" fill="#ff0000" />
+<circle cx="249.4" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="249.4" cy="63.150689655172414" r="1" fill="#ff0000" />
+<circle cx="249.4" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="276" cy="51.11137931034482" r="1" fill="#ff0000" />
+<circle cx="276" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="276" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="276" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="276" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="289.3" cy="51.11137931034482" r="1" fill="#ff0000" />
+</g>
+<g id="cut">
+<path d="M 10 11 L 289.3 11 L 289.3 81.19 L 10 81.19 Z" fill="none" stroke="#0000ff" stroke-width="0.1" />
+</g>
 </svg>"##.to_string()
         );
 
         assert_eq!(
             res[1],
-            r##"<svg version="1.1" xmlns="http://www.w3.org/2000/svg">
-<line x1="10mm" y1="10mm" x2="98mm" y2="10mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="12.006551724137932mm" x2="98mm" y2="12.006551724137932mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="14.013103448275862mm" x2="98mm" y2="14.013103448275862mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="16.019655172413792mm" x2="98mm" y2="16.019655172413792mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="18.026206896551724mm" x2="98mm" y2="18.026206896551724mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="20.032758620689656mm" x2="98mm" y2="20.032758620689656mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="22.039310344827584mm" x2="98mm" y2="22.039310344827584mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="24.045862068965516mm" x2="98mm" y2="24.045862068965516mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="26.052413793103447mm" x2="98mm" y2="26.052413793103447mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="28.05896551724138mm" x2="98mm" y2="28.05896551724138mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="30.06551724137931mm" x2="98mm" y2="30.06551724137931mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="32.07206896551724mm" x2="98mm" y2="32.07206896551724mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="34.07862068965517mm" x2="98mm" y2="34.07862068965517mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="36.0851724137931mm" x2="98mm" y2="36.0851724137931mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="38.09172413793103mm" x2="98mm" y2="38.09172413793103mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="40.09827586206896mm" x2="98mm" y2="40.09827586206896mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="42.104827586206895mm" x2="98mm" y2="42.104827586206895mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="44.11137931034482mm" x2="98mm" y2="44.11137931034482mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="46.11793103448276mm" x2="98mm" y2="46.11793103448276mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="48.12448275862069mm" x2="98mm" y2="48.12448275862069mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="50.13103448275862mm" x2="98mm" y2="50.13103448275862mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="52.13758620689655mm" x2="98mm" y2="52.13758620689655mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="54.14413793103448mm" x2="98mm" y2="54.14413793103448mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="56.150689655172414mm" x2="98mm" y2="56.150689655172414mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="58.15724137931034mm" x2="98mm" y2="58.15724137931034mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="60.16379310344827mm" x2="98mm" y2="60.16379310344827mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="62.170344827586206mm" x2="98mm" y2="62.170344827586206mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="64.17689655172413mm" x2="98mm" y2="64.17689655172413mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="66.18344827586206mm" x2="98mm" y2="66.18344827586206mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="68.19mm" x2="98mm" y2="68.19mm" stroke="#000000" stroke_width="1mm" />
-<line x1="10mm" y1="5mm" x2="10mm" y2="73.19mm" stroke="#ff00ff" stroke_width="1mm" />
-<line x1="98mm" y1="5mm" x2="98mm" y2="73.19mm" stroke="#ff00ff" stroke_width="1mm" />
-<line x1="10mm" y1="5mm" x2="98mm" y2="5mm" stroke="#00ff00" stroke_width="1mm" />
-<line x1="10mm" y1="73.19mm" x2="98mm" y2="73.19mm" stroke="#00ff00" stroke_width="1mm" />
-<circle cx="18mm" cy="44.11137931034482mm" r="1mm" fill="#ff0000" />
-<circle cx="18mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="18mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="18mm" cy="50.13103448275862mm" r="1mm" fill="#ff0000" />
-<circle cx="26mm" cy="48.12448275862069mm" r="1mm" fill="#ff0000" />
-<circle cx="34mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="34mm" cy="64.17689655172413mm" r="1mm" fill="#ff0000" />
-<circle cx="34mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="34mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
-<circle cx="50mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="50mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="50mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="50mm" cy="50.13103448275862mm" r="1mm" fill="#ff0000" />
-<circle cx="66mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="66mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="66mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="66mm" cy="52.13758620689655mm" r="1mm" fill="#ff0000" />
-<circle cx="74mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="82mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="82mm" cy="64.17689655172413mm" r="1mm" fill="#ff0000" />
-<circle cx="82mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="82mm" cy="56.150689655172414mm" r="1mm" fill="#ff0000" />
-<circle cx="90mm" cy="60.16379310344827mm" r="1mm" fill="#ff0000" />
-<circle cx="98mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="98mm" cy="62.170344827586206mm" r="1mm" fill="#ff0000" />
-<circle cx="98mm" cy="58.15724137931034mm" r="1mm" fill="#ff0000" />
-<circle cx="98mm" cy="54.14413793103448mm" r="1mm" fill="#ff0000" />
+            r##"<svg version="1.1" xmlns="http://www.w3.org/2000/svg" width="297mm" height="210mm" viewBox="0 0 297 210">
+<g id="glue">
+<rect x="10" y="11" width="10" height="70.19" fill="#e0e0e0" stroke="none" />
+</g>
+<g id="staff">
+<line x1="10" y1="17" x2="272.70000000000005" y2="17" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="19.006551724137932" x2="272.70000000000005" y2="19.006551724137932" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="21.013103448275864" x2="272.70000000000005" y2="21.013103448275864" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="23.019655172413792" x2="272.70000000000005" y2="23.019655172413792" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="25.026206896551724" x2="272.70000000000005" y2="25.026206896551724" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="27.032758620689656" x2="272.70000000000005" y2="27.032758620689656" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="29.039310344827584" x2="272.70000000000005" y2="29.039310344827584" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="31.045862068965516" x2="272.70000000000005" y2="31.045862068965516" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="33.05241379310345" x2="272.70000000000005" y2="33.05241379310345" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="35.058965517241376" x2="272.70000000000005" y2="35.058965517241376" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="37.06551724137931" x2="272.70000000000005" y2="37.06551724137931" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="39.07206896551724" x2="272.70000000000005" y2="39.07206896551724" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="41.07862068965517" x2="272.70000000000005" y2="41.07862068965517" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="43.0851724137931" x2="272.70000000000005" y2="43.0851724137931" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="45.09172413793103" x2="272.70000000000005" y2="45.09172413793103" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="47.09827586206896" x2="272.70000000000005" y2="47.09827586206896" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="49.104827586206895" x2="272.70000000000005" y2="49.104827586206895" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="51.11137931034482" x2="272.70000000000005" y2="51.11137931034482" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="53.11793103448276" x2="272.70000000000005" y2="53.11793103448276" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="55.12448275862069" x2="272.70000000000005" y2="55.12448275862069" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="57.13103448275862" x2="272.70000000000005" y2="57.13103448275862" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="59.13758620689655" x2="272.70000000000005" y2="59.13758620689655" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="61.14413793103448" x2="272.70000000000005" y2="61.14413793103448" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="63.150689655172414" x2="272.70000000000005" y2="63.150689655172414" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="65.15724137931034" x2="272.70000000000005" y2="65.15724137931034" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="67.16379310344827" x2="272.70000000000005" y2="67.16379310344827" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="69.1703448275862" x2="272.70000000000005" y2="69.1703448275862" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="71.17689655172413" x2="272.70000000000005" y2="71.17689655172413" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="73.18344827586206" x2="272.70000000000005" y2="73.18344827586206" stroke="#000000" stroke-width="1" />
+<line x1="10" y1="75.19" x2="272.70000000000005" y2="75.19" stroke="#000000" stroke-width="1" />
+</g>
+<g id="score">
+<line x1="10" y1="12" x2="10" y2="80.19" stroke="#ff00ff" stroke-width="1" />
+<line x1="272.70000000000005" y1="12" x2="272.70000000000005" y2="80.19" stroke="#ff00ff" stroke-width="1" />
+<line x1="10" y1="12" x2="272.70000000000005" y2="12" stroke="#00ff00" stroke-width="1" />
+<line x1="10" y1="80.19" x2="272.70000000000005" y2="80.19" stroke="#00ff00" stroke-width="1" />
+</g>
+<g id="decorations">
+<line x1="10" y1="8" x2="10" y2="11" stroke="#ff00ff" stroke-width="1" />
+<line x1="10" y1="81.19" x2="10" y2="84.19" stroke="#ff00ff" stroke-width="1" />
+<line x1="262.70000000000005" y1="8" x2="262.70000000000005" y2="11" stroke="#ff00ff" stroke-width="1" />
+<line x1="262.70000000000005" y1="81.19" x2="262.70000000000005" y2="84.19" stroke="#ff00ff" stroke-width="1" />
+<line x1="4" y1="43.095" x2="7" y2="46.095" stroke="#ff00ff" stroke-width="1" />
+<line x1="4" y1="49.095" x2="7" y2="46.095" stroke="#ff00ff" stroke-width="1" />
+<line x1="275.70000000000005" y1="43.095" x2="278.70000000000005" y2="46.095" stroke="#ff00ff" stroke-width="1" />
+<line x1="275.70000000000005" y1="49.095" x2="278.70000000000005" y2="46.095" stroke="#ff00ff" stroke-width="1" />
+</g>
+<g id="labels">
+<text x="5.5" y="51.595" font-size="2" font-family="sans-serif" fill="#ff00ff" text-anchor="middle">p. 1</text>
+<text x="277.20000000000005" y="51.595" font-size="2" font-family="sans-serif" fill="#ff00ff" text-anchor="middle">p. 3</text>
+<text x="10" y="7" font-size="3" font-family="sans-serif" fill="#000000" text-anchor="start">Piano</text>
+<text x="148.5" y="7" font-size="3" font-family="sans-serif" fill="#000000" text-anchor="middle">30-note</text>
+<text x="287" y="7" font-size="3" font-family="sans-serif" fill="#000000" text-anchor="end">page 2 of 3</text>
+</g>
+<g id="sprockets">
+<circle cx="40.69999999999999" cy="14.5" r="1" fill="#ffff00" />
+<circle cx="40.69999999999999" cy="77.69" r="1" fill="#ffff00" />
+<circle cx="90.69999999999999" cy="14.5" r="1" fill="#ffff00" />
+<circle cx="90.69999999999999" cy="77.69" r="1" fill="#ffff00" />
+<circle cx="140.7" cy="14.5" r="1" fill="#ffff00" />
+<circle cx="140.7" cy="77.69" r="1" fill="#ffff00" />
+<circle cx="190.7" cy="14.5" r="1" fill="#ffff00" />
+<circle cx="190.7" cy="77.69" r="1" fill="#ffff00" />
+<circle cx="240.7" cy="14.5" r="1" fill="#ffff00" />
+<circle cx="240.7" cy="77.69" r="1" fill="#ffff00" />
+</g>
+<g id="holes">
+<circle cx="20" cy="51.11137931034482" r="1" fill="#ff0000" />
+<circle cx="33.3" cy="51.11137931034482" r="1" fill="#ff0000" />
+<circle cx="33.3" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="33.3" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="33.3" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="33.3" cy="39.07206896551724" r="1" fill="#ff0000" />
+<circle cx="33.3" cy="57.13103448275862" r="1" fill="#ff0000" />
+<circle cx="46.6" cy="55.12448275862069" r="1" fill="#ff0000" />
+<circle cx="59.900000000000006" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="59.900000000000006" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="59.900000000000006" cy="71.17689655172413" r="1" fill="#ff0000" />
+<circle cx="59.900000000000006" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="59.900000000000006" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="86.5" cy="63.150689655172414" r="1" fill="#ff0000" />
+<circle cx="86.5" cy="73.18344827586206" r="1" fill="#ff0000" />
+<circle cx="86.5" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="86.5" cy="63.150689655172414" r="1" fill="#ff0000" />
+<circle cx="86.5" cy="57.13103448275862" r="1" fill="#ff0000" />
+<circle cx="113.10000000000001" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="113.10000000000001" cy="59.13758620689655" r="1" fill="#ff0000" />
+<circle cx="113.10000000000001" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="113.10000000000001" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="113.10000000000001" cy="59.13758620689655" r="1" fill="#ff0000" />
+<circle cx="126.4" cy="63.150689655172414" r="1" fill="#ff0000" />
+<circle cx="139.7" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="139.7" cy="71.17689655172413" r="1" fill="#ff0000" />
+<circle cx="139.7" cy="71.17689655172413" r="1" fill="#ff0000" />
+<circle cx="139.7" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="139.7" cy="63.150689655172414" r="1" fill="#ff0000" />
+<circle cx="153" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="166.3" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="166.3" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="166.3" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="166.3" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="166.3" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="219.5" cy="51.11137931034482" r="1" fill="#ff0000" />
+<circle cx="219.5" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="219.5" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="219.5" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="219.5" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="232.8" cy="51.11137931034482" r="1" fill="#ff0000" />
+<circle cx="246.10000000000002" cy="51.11137931034482" r="1" fill="#ff0000" />
+<circle cx="246.10000000000002" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="246.10000000000002" cy="69.1703448275862" r="1" fill="#ff0000" />
+<circle cx="246.10000000000002" cy="67.16379310344827" r="1" fill="#ff0000" />
+<circle cx="246.10000000000002" cy="39.07206896551724" r="1" fill="#ff0000" />
+<circle cx="246.10000000000002" cy="57.13103448275862" r="1" fill="#ff0000" />
+<circle cx="259.4" cy="55.12448275862069" r="1" fill="#ff0000" />
+<circle cx="272.70000000000005" cy="61.14413793103448" r="1" fill="#ff0000" />
+<circle cx="272.70000000000005" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="272.70000000000005" cy="71.17689655172413" r="1" fill="#ff0000" />
+<circle cx="272.70000000000005" cy="65.15724137931034" r="1" fill="#ff0000" />
+<circle cx="272.70000000000005" cy="61.14413793103448" r="1" fill="#ff0000" />
+</g>
+<g id="cut">
+<path d="M 10 11 L 272.70000000000005 11 L 272.70000000000005 81.19 L 10 81.19 Z" fill="none" stroke="#0000ff" stroke-width="0.1" />
+</g>
 </svg>"##.to_string()
         );
     }
+
+    /// Compares the first page rendered as png with the golden image.
+    /// To update the golden image run 'convert -i meg_wiwauf_laminat1.mid -o ./out/ -qt -f png --dpi 50' and copy ./out/0.png to ./tests/golden/0.png
+    #[test]
+    fn test_png() {
+        let args = [
+            "program_name",
+            "convert",
+            "-i",
+            "meg_wiwauf_laminat1.mid",
+            "-o",
+            "./out/",
+            "-qt",
+            "-f",
+            "png",
+            "--dpi",
+            "50",
+        ];
+
+        let command = crate::command::get_command();
+        let matches = command.get_matches_from(args);
+        let res = match matches.subcommand() {
+            Some(("convert", sub_m)) => MusicBoxConvert::new(sub_m).run_output_png().unwrap(),
+            _ => panic!("Invalid subcommand"),
+        };
+
+        assert_eq!(
+            decode_png(&res[0]),
+            decode_png(include_bytes!("../tests/golden/0.png"))
+        );
+    }
+
+    /// Converts the midi file and decodes the svgs again. The decoded notes have to be the playable notes of the midi file
+    #[test]
+    fn test_decode() {
+        let args = [
+            "program_name",
+            "convert",
+            "-i",
+            "meg_wiwauf_laminat1.mid",
+            "-o",
+            "./out/",
+            "-qt",
+        ];
+
+        let command = crate::command::get_command();
+        let documents = run(command.get_matches_from(args));
+
+        let data = std::fs::read("meg_wiwauf_laminat1.mid").unwrap();
+        let smf = midly::Smf::parse(&data).unwrap();
+        let music_box = MusicBox::load("./box.json", None, false).unwrap();
+        let expected = Track::from_midi_tracks(
+            &smf.tracks[0..1],
+            None,
+            &TempoMap::from_midi_tracks(&smf.tracks, &smf.header.timing),
+            &music_box,
+            &true,
+            0,
+        );
+
+        // The converter fits the minimum distance between two notes to the minimum note distance of the music box
+        let mm_per_beat = music_box.min_note_distance_mm / expected.min_distance() as f64 * 480f64;
+        let mm_per_beat = mm_per_beat.to_string();
+        let args = [
+            "program_name",
+            "decode",
+            "-i",
+            "0.svg",
+            "-o",
+            "./out/decoded.mid",
+            "--mm-per-beat",
+            &mm_per_beat,
+            "-q",
+        ];
+
+        let command = crate::command::get_command();
+        let matches = command.get_matches_from(args);
+        let res = match matches.subcommand() {
+            Some(("decode", sub_m)) => MusicBoxDecode::new(sub_m)
+                .run_output_track(
+                    &documents
+                        .into_iter()
+                        .map(String::into_bytes)
+                        .collect::<Vec<Vec<u8>>>(),
+                )
+                .unwrap(),
+            _ => panic!("Invalid subcommand"),
+        };
+
+        let notes = |track: &Track| {
+            let mut notes = track
+                .iter()
+                .map(|el| (el.abs, el.note.to_string()))
+                .collect::<Vec<(u64, String)>>();
+            notes.sort();
+            notes
+        };
+        assert_eq!(notes(&res), notes(&expected));
+    }
+
+    /// Draws a strip with one hole on every note line on a dark background and scans it
+    #[test]
+    fn test_scan() {
+        let music_box = MusicBox::load("./box.json", None, false).unwrap();
+        // At 254 dpi a millimetre is ten pixels. The strip is 150mm by 70mm with the note lines in the centre
+        let (width, height) = (1700usize, 900usize);
+        let (left, top) = (100usize, 100usize);
+        let staff_y = (70f64 - music_box.strip_height_mm) / 2f64;
+        let mut pixels = vec![30u8; width * height];
+        for y in top..top + 700 {
+            for x in left..left + 1500 {
+                pixels[y * width + x] = 235;
+            }
+        }
+
+        let mut expected = Vec::<(u64, String)>::new();
+        for i in 0..music_box.note_count() {
+            let index = i * 7 % music_box.note_count();
+            let x_mm = 10f64 + i as f64 * 4.5f64;
+            let y_mm = staff_y + music_box.note_y_mm(index);
+            // Printed note lines go through the holes
+            for x in left..left + 1500 {
+                pixels[(top + (y_mm * 10f64) as usize) * width + x] = 60;
+            }
+            for y in top..top + 700 {
+                for x in left..left + 1500 {
+                    let dx = x as f64 + 0.5f64 - left as f64 - x_mm * 10f64;
+                    let dy = y as f64 + 0.5f64 - top as f64 - y_mm * 10f64;
+                    if dx * dx + dy * dy <= 64f64 {
+                        pixels[y * width + x] = 30;
+                    }
+                }
+            }
+            // 10mm per beat and 480 ticks per beat
+            expected.push((
+                (x_mm * 48f64).round() as u64,
+                music_box.get_note(index).unwrap().to_string(),
+            ));
+        }
+
+        let mut png = Vec::<u8>::new();
+        let mut encoder = png::Encoder::new(&mut png, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder
+            .write_header()
+            .unwrap()
+            .write_image_data(&pixels)
+            .unwrap();
+
+        let args = [
+            "program_name",
+            "decode",
+            "-i",
+            "scan.png",
+            "-o",
+            "./out/decoded.mid",
+            "--mm-per-beat",
+            "10",
+            "--dpi",
+            "254",
+            "-q",
+        ];
+
+        let command = crate::command::get_command();
+        let matches = command.get_matches_from(args);
+        let res = match matches.subcommand() {
+            Some(("decode", sub_m)) => MusicBoxDecode::new(sub_m).run_output_track(&[png]).unwrap(),
+            _ => panic!("Invalid subcommand"),
+        };
+
+        let mut notes = res
+            .iter()
+            .map(|el| (el.abs, el.note.to_string()))
+            .collect::<Vec<(u64, String)>>();
+        notes.sort();
+        expected.sort();
+        assert_eq!(notes, expected);
+    }
 }
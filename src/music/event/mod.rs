@@ -7,7 +7,7 @@ use super::note::Note;
 pub struct Event {
     /// The Note of the Event
     pub note: Note,
-    /// The absolute time of the <code>Event</code>
+    /// The absolute time of the <code>Event</code> in tempo-adjusted MidiTicks. See [crate::music::tempo_map::TempoMap]
     pub abs: u64,
     /// The velocity of the Event
     pub vel: u8,
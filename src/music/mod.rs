@@ -1,4 +1,6 @@
 pub mod event;
 pub mod music_box;
 pub mod note;
+pub mod tempo_map;
+pub mod time_signature;
 pub mod track;
@@ -1,9 +1,120 @@
+// std
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+};
+
 // Internal
 use super::MusicBox;
 use crate::music::note::Note;
 use crate::prelude::*;
 
+/// The music boxes that are built into the converter. These are used if there is no box file. Kept apart from the default box file, so editing that doesn't change them
+const BUILTIN_BOXES: &str = include_str!("builtin.json");
+
 impl MusicBox {
+    /// Deserializes a box file. A box file contains a list of music boxes or, in older files, a single music box.
+    pub fn list_from_reader<R: Read>(reader: R) -> Result<Vec<Self>> {
+        let value: serde_json::Value = serde_json::from_reader(reader).to_res()?;
+        let boxes: Vec<Self> = match value {
+            serde_json::Value::Array(_) => serde_json::from_value(value).to_res()?,
+            _ => vec![serde_json::from_value(value).to_res()?],
+        };
+
+        for music_box in boxes.iter() {
+            if let Some(t) = &music_box.note_positions_mm {
+                if t.len() != music_box.notes.len() {
+                    return Err(Error::Generic(format!(
+                        "Music box '{0}' has {1} notes but {2} note positions",
+                        music_box.name,
+                        music_box.notes.len(),
+                        t.len()
+                    )));
+                }
+                for (i, position) in t.iter().enumerate() {
+                    if !position.is_finite()
+                        || *position < 0f64
+                        || *position > music_box.strip_height_mm
+                    {
+                        return Err(Error::Generic(format!(
+                            "Music box '{0}' has the note position {1} which isn't between 0 and the strip height of {2}",
+                            music_box.name, position, music_box.strip_height_mm
+                        )));
+                    }
+                    if t[..i].contains(position) {
+                        return Err(Error::Generic(format!(
+                            "Music box '{0}' has the note position {1} more than once",
+                            music_box.name, position
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(boxes)
+    }
+
+    /// Loads the music box with the given name from a box file, or the first one if no name is given.
+    /// Falls back to the built-in music boxes if the box file doesn't contain a box with that name.
+    /// A missing box file is only an error if `required` is set, otherwise only the built-in music boxes are used.
+    pub fn load(path: &str, name: Option<&String>, required: bool) -> Result<Self> {
+        let mut boxes = match File::open(path) {
+            Ok(t) => Self::list_from_reader(BufReader::new(t))?,
+            Err(e) => {
+                if required {
+                    return Err(Error::IOError(Box::new(e), Box::new(path.to_string())));
+                }
+                warn!("Couldn't open box file '{path}'. Using the built-in music boxes.");
+                Vec::<Self>::new()
+            }
+        };
+
+        let index = match name {
+            None => match boxes.is_empty() {
+                true => None,
+                false => Some(0),
+            },
+            Some(t) => boxes.iter().position(|el| el.name == *t),
+        };
+
+        let music_box = match index {
+            Some(t) => boxes.swap_remove(t),
+            None => {
+                let mut builtin = Self::builtin();
+                let index = match name {
+                    None => Some(0),
+                    Some(t) => builtin.iter().position(|el| el.name == *t),
+                };
+                match index {
+                    Some(t) => {
+                        info!("Using the built-in music box '{}'.", builtin[t].name);
+                        builtin.swap_remove(t)
+                    }
+                    None => {
+                        let mut names = boxes
+                            .iter()
+                            .map(|el| el.name.clone())
+                            .collect::<Vec<String>>();
+                        names.extend(builtin.iter().map(|el| el.name.clone()));
+                        return Err(Error::Generic(format!(
+                            "There is no music box named '{0}'. Available music boxes are: {1}",
+                            name.unwrap(),
+                            names.join(", ")
+                        )));
+                    }
+                }
+            }
+        };
+
+        info!("Using music box '{}'.", music_box.name);
+        Ok(music_box)
+    }
+
+    /// Returns the music boxes that are built into the converter
+    pub fn builtin() -> Vec<Self> {
+        Self::list_from_reader(BUILTIN_BOXES.as_bytes()).unwrap()
+    }
+
     /// Returns `true` if the note is playable by the musicbox
     pub fn is_valid_note(&self, note: &Note) -> bool {
         self.notes.contains(note)
@@ -13,13 +124,74 @@ impl MusicBox {
         self.strip_height_mm / (self.notes.len() as f64 - 1f64)
     }
 
+    /// Returns the distance of the note line with the given index from the top note line in millimetres
+    pub fn note_y_mm(&self, index: usize) -> f64 {
+        match &self.note_positions_mm {
+            Some(t) => t[index],
+            None => (self.notes.len() - 1 - index) as f64 * self.vertical_note_distance(),
+        }
+    }
+
+    /// Returns the distance of the highest and the lowest note line from the top note line in millimetres. These are the top and bottom edge of the staff
+    pub fn note_y_range_mm(&self) -> (f64, f64) {
+        (0..self.notes.len())
+            .map(|i| self.note_y_mm(i))
+            .fold((f64::MAX, f64::MIN), |(min, max), el| {
+                (min.min(el), max.max(el))
+            })
+    }
+
     /// Returns the number of notes
     pub fn note_count(&self) -> usize {
         self.notes.len()
     }
 
+    /// Gets the [super::Note] at an index. Zero is the lowest note
+    pub fn get_note(&self, index: usize) -> Option<&Note> {
+        self.notes.get(index)
+    }
+
     /// Gets the index of a [super::Note].
     pub fn get_index(&self, note: &Note) -> Option<usize> {
         self.notes.iter().position(|el| *el == *note)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_from_reader() {
+        let single =
+            r#"{ "strip_height_mm": 10, "min_note_distance_mm": 2, "notes": ["C4", "D4"] }"#;
+        let boxes = MusicBox::list_from_reader(single.as_bytes()).unwrap();
+        assert_eq!(boxes.len(), 1);
+        assert_eq!(boxes[0].note_count(), 2);
+
+        let list = format!("[{single}, {single}]");
+        assert_eq!(
+            MusicBox::list_from_reader(list.as_bytes()).unwrap().len(),
+            2
+        );
+
+        let positions = r#"{ "strip_height_mm": 10, "min_note_distance_mm": 2, "notes": ["C4", "D4", "E4"], "note_positions_mm": [9.5, 4, 0.5] }"#;
+        let boxes = MusicBox::list_from_reader(positions.as_bytes()).unwrap();
+        assert_eq!(boxes[0].note_y_mm(1), 4f64);
+        assert_eq!(boxes[0].note_y_mm(2), 0.5f64);
+        assert_eq!(boxes[0].note_y_range_mm(), (0.5f64, 9.5f64));
+        assert_eq!(MusicBox::builtin()[0].note_y_mm(0), 58.19f64);
+        assert_eq!(MusicBox::builtin()[0].note_y_range_mm(), (0f64, 58.19f64));
+
+        for invalid in ["[9.5, 4]", "[-1, 4, 0.5]", "[10.5, 4, 0.5]", "[9.5, 4, 4]"] {
+            let invalid = positions.replace("[9.5, 4, 0.5]", invalid);
+            assert!(MusicBox::list_from_reader(invalid.as_bytes()).is_err());
+        }
+
+        let builtin = MusicBox::builtin();
+        assert_eq!(builtin[0].name, "30-note");
+        assert!(builtin
+            .iter()
+            .all(|el| el.note_count() == el.name[..2].parse::<usize>().unwrap()));
+    }
+}
@@ -2,28 +2,47 @@
 pub mod functions;
 
 // serde_derive
-use serde_derive::Deserialize;
+use serde_derive::{Deserialize, Serialize};
 
 // Internal
 use super::note::Note;
 
 /// A music box
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct MusicBox {
+    /// The name used to choose the music box from the box file
+    #[serde(default)]
+    pub name: String,
     /// The strip height in millimetres. The strip is the length of all note lines plus the border
     pub strip_height_mm: f64,
     /// The minimum distance the music box can play two notes back to back. We need this to calculate how much we need to stretch the notes for all of them to playable
     pub min_note_distance_mm: f64,
+    /// How far the strip moves during one turn of the crank. Needed for laying the strip out by crank speed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mm_per_crank_turn: Option<f64>,
     /// The Notes this music box can play
     notes: Vec<Note>,
+    /// The distance of each note line from the top note line in millimetres. In the same order as the notes. The teeth are evenly spaced across the strip height if this isn't set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    note_positions_mm: Option<Vec<f64>>,
 }
 
 impl MusicBox {
-    pub fn new(strip_height_mm: f64, min_note_distance_mm: f64, notes: Vec<Note>) -> Self {
+    pub fn new(
+        name: String,
+        strip_height_mm: f64,
+        min_note_distance_mm: f64,
+        mm_per_crank_turn: Option<f64>,
+        notes: Vec<Note>,
+        note_positions_mm: Option<Vec<f64>>,
+    ) -> Self {
         Self {
+            name,
             strip_height_mm,
             min_note_distance_mm,
+            mm_per_crank_turn,
             notes,
+            note_positions_mm,
         }
     }
 }
@@ -1,46 +1,29 @@
+// std
+use std::str::FromStr;
+
 // midly
 use midly::{num::u7, MidiMessage};
 
+// serde
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
 // Internal
 use super::Note;
+use crate::prelude::*;
 
 impl Note {
-    /// Returns a `Note` from a midi pitch.
+    /// Returns a `Note` from a midi pitch. Midi pitch 0 is C-1 and 127 is G9.
     pub fn from_midi_pitch(pitch: midly::num::u7) -> Self {
         let pitch = pitch.as_int();
-        let octave = ((pitch - 12) / 12) as i8;
-        match pitch % 12 {
-            0 => Note::C(octave),
-            1 => Note::CS(octave),
-            2 => Note::D(octave),
-            3 => Note::DS(octave),
-            4 => Note::E(octave),
-            5 => Note::F(octave),
-            6 => Note::FS(octave),
-            7 => Note::G(octave),
-            8 => Note::GS(octave),
-            9 => Note::A(octave),
-            10 => Note::AS(octave),
-            11 => Note::B(octave),
-            _ => panic!("/music/note/function.rs/from_midi_pitch(). Impossible value!"),
-        }
+        Self::from_semitone(pitch % 12, (pitch / 12) as i8 - 1)
     }
 
-    /// Returns a midi pitch from a `Note`
-    pub fn to_midi_pitch(&self) -> midly::num::u7 {
-        match self {
-            Note::C(octave) => u7::from_int_lossy(((octave * 12) + 12) as u8),
-            Note::CS(octave) => u7::from_int_lossy((1 + (octave * 12) + 12) as u8),
-            Note::D(octave) => u7::from_int_lossy((2 + (octave * 12) + 12) as u8),
-            Note::DS(octave) => u7::from_int_lossy((3 + (octave * 12) + 12) as u8),
-            Note::E(octave) => u7::from_int_lossy((4 + (octave * 12) + 12) as u8),
-            Note::F(octave) => u7::from_int_lossy((5 + (octave * 12) + 12) as u8),
-            Note::FS(octave) => u7::from_int_lossy((6 + (octave * 12) + 12) as u8),
-            Note::G(octave) => u7::from_int_lossy((7 + (octave * 12) + 12) as u8),
-            Note::GS(octave) => u7::from_int_lossy((8 + (octave * 12) + 12) as u8),
-            Note::A(octave) => u7::from_int_lossy((9 + (octave * 12) + 12) as u8),
-            Note::AS(octave) => u7::from_int_lossy((10 + (octave * 12) + 12) as u8),
-            Note::B(octave) => u7::from_int_lossy((11 + (octave * 12) + 12) as u8),
+    /// Returns a midi pitch from a `Note`. Returns `None` if the `Note` is outside of the midi range, which goes from C-1 to G9.
+    pub fn to_midi_pitch(&self) -> Option<midly::num::u7> {
+        let pitch = (*self.get_octave() as i16 + 1) * 12 + self.semitone() as i16;
+        match u8::try_from(pitch) {
+            Ok(t) => u7::try_from(t),
+            Err(_) => None,
         }
     }
 
@@ -62,6 +45,49 @@ impl Note {
         }
     }
 
+    /// Returns the position of a `Note` inside its octave. C is 0 and B is 11
+    pub fn semitone(&self) -> u8 {
+        match self {
+            Note::C(_) => 0,
+            Note::CS(_) => 1,
+            Note::D(_) => 2,
+            Note::DS(_) => 3,
+            Note::E(_) => 4,
+            Note::F(_) => 5,
+            Note::FS(_) => 6,
+            Note::G(_) => 7,
+            Note::GS(_) => 8,
+            Note::A(_) => 9,
+            Note::AS(_) => 10,
+            Note::B(_) => 11,
+        }
+    }
+
+    /// Returns a `Note` from its position inside an octave. C is 0 and B is 11
+    pub fn from_semitone(semitone: u8, octave: i8) -> Self {
+        match semitone % 12 {
+            0 => Note::C(octave),
+            1 => Note::CS(octave),
+            2 => Note::D(octave),
+            3 => Note::DS(octave),
+            4 => Note::E(octave),
+            5 => Note::F(octave),
+            6 => Note::FS(octave),
+            7 => Note::G(octave),
+            8 => Note::GS(octave),
+            9 => Note::A(octave),
+            10 => Note::AS(octave),
+            11 => Note::B(octave),
+            _ => panic!("/music/note/function.rs/from_semitone(). Impossible value!"),
+        }
+    }
+
+    /// Shifts a `Note` by a number of semitones. Negative values shift it down
+    pub fn shift(&self, semitones: i8) -> Self {
+        let total = *self.get_octave() as i16 * 12 + self.semitone() as i16 + semitones as i16;
+        Self::from_semitone(total.rem_euclid(12) as u8, total.div_euclid(12) as i8)
+    }
+
     /// Returns a reference to an octave from a `Note`
     pub fn get_octave(&self) -> &i8 {
         match self {
@@ -103,3 +129,146 @@ impl std::fmt::Display for Note {
         )
     }
 }
+
+impl FromStr for Note {
+    type Err = Error;
+
+    /// Parses scientific pitch notation like "C4", "F#3", "Gb3" or "A♯4". Sharps can be written as '#', '♯' or 'S', flats as 'b' or '♭'.
+    fn from_str(s: &str) -> Result<Self> {
+        let error = || {
+            Error::Generic(format!(
+                "'{s}' is not a valid note. Valid notes look like 'C4', 'F#3' or 'Gb3'."
+            ))
+        };
+        let mut chars = s.trim().chars().peekable();
+
+        let mut semitone: i16 = match chars.next().map(|t| t.to_ascii_uppercase()) {
+            Some('C') => 0,
+            Some('D') => 2,
+            Some('E') => 4,
+            Some('F') => 5,
+            Some('G') => 7,
+            Some('A') => 9,
+            Some('B') => 11,
+            _ => return Err(error()),
+        };
+
+        while let Some(t) = chars.peek() {
+            match t {
+                '#' | '♯' | 'S' | 's' => semitone += 1,
+                'b' | '♭' => semitone -= 1,
+                _ => break,
+            }
+            chars.next();
+        }
+
+        let octave: i16 = match chars.collect::<String>().parse() {
+            Ok(t) => t,
+            Err(_) => return Err(error()),
+        };
+
+        // Accidentals can move a note into the next or previous octave. B♯3 is C4 and C♭4 is B3
+        let total = octave * 12 + semitone;
+        match i8::try_from(total.div_euclid(12)) {
+            Ok(t) => Ok(Self::from_semitone(total.rem_euclid(12) as u8, t)),
+            Err(_) => Err(error()),
+        }
+    }
+}
+
+impl Serialize for Note {
+    /// Serializes to ascii scientific pitch notation like "F#3"
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string().replace('♯', "#"))
+    }
+}
+
+impl<'de> Deserialize<'de> for Note {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(NoteVisitor)
+    }
+}
+
+/// Deserializes a `Note` from a string like "F#3" or from the old externally tagged form `{ "FS": 3 }`
+struct NoteVisitor;
+
+impl<'de> de::Visitor<'de> for NoteVisitor {
+    type Value = Note;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "a note like \"F#3\" or {{ \"FS\": 3 }}")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let (name, octave) = match map.next_entry::<String, i8>()? {
+            Some(t) => t,
+            None => return Err(de::Error::custom("expected a note name with an octave")),
+        };
+        // The variant names ("FS") are valid note names, as 'S' is accepted as a sharp
+        format!("{name}{octave}").parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Note;
+    use midly::num::u7;
+
+    #[test]
+    fn midi_pitch_round_trip() {
+        for pitch in 0..=127u8 {
+            let note = Note::from_midi_pitch(u7::from(pitch));
+            assert_eq!(note.to_midi_pitch(), Some(u7::from(pitch)), "{note}");
+        }
+    }
+
+    #[test]
+    fn midi_pitch_range() {
+        assert_eq!(Note::from_midi_pitch(u7::from(0)), Note::C(-1));
+        assert_eq!(Note::from_midi_pitch(u7::from(11)), Note::B(-1));
+        assert_eq!(Note::from_midi_pitch(u7::from(60)), Note::C(4));
+        assert_eq!(Note::from_midi_pitch(u7::from(127)), Note::G(9));
+
+        assert_eq!(Note::B(-2).to_midi_pitch(), None);
+        assert_eq!(Note::GS(9).to_midi_pitch(), None);
+        assert_eq!(Note::C(10).to_midi_pitch(), None);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!("C4".parse::<Note>().unwrap(), Note::C(4));
+        assert_eq!("F#3".parse::<Note>().unwrap(), Note::FS(3));
+        assert_eq!("Gb3".parse::<Note>().unwrap(), Note::FS(3));
+        assert_eq!("A♯4".parse::<Note>().unwrap(), Note::AS(4));
+        assert_eq!("B♭4".parse::<Note>().unwrap(), Note::AS(4));
+        assert_eq!("Cb4".parse::<Note>().unwrap(), Note::B(3));
+        assert_eq!("B#3".parse::<Note>().unwrap(), Note::C(4));
+        assert_eq!("c-1".parse::<Note>().unwrap(), Note::C(-1));
+
+        assert!("H4".parse::<Note>().is_err());
+        assert!("C".parse::<Note>().is_err());
+        assert!("F#x".parse::<Note>().is_err());
+    }
+
+    #[test]
+    fn serde() {
+        let notes: Vec<Note> =
+            serde_json::from_str(r#"["F#3", "Gb3", { "FS": 3 }, { "C": -1 }]"#).unwrap();
+        assert_eq!(
+            notes,
+            vec![Note::FS(3), Note::FS(3), Note::FS(3), Note::C(-1)]
+        );
+
+        assert_eq!(
+            serde_json::to_string(&vec![Note::FS(3), Note::C(4)]).unwrap(),
+            r#"["F#3","C4"]"#
+        );
+    }
+}
@@ -1,11 +1,9 @@
 // Modules
 pub mod functions;
 
-// serde_derive
-use serde_derive::Deserialize;
-
 /// My note representation. i8 is the octave of the note
-#[derive(Clone, Debug, PartialEq, Deserialize)]
+/// (De)serializes from and to scientific pitch strings like "F#3". The old form `{ "FS": 3 }` can still be deserialized.
+#[derive(Clone, Debug, PartialEq)]
 pub enum Note {
     C(i8),
     CS(i8),
@@ -0,0 +1,159 @@
+// Internal
+use super::{TempoMap, TempoSegment};
+
+// midly
+use midly::{MetaMessage, Timing, Track as MidiTrack, TrackEventKind};
+
+/// The tempo midi assumes if there is no tempo event. 120 beats per minute
+const DEFAULT_TEMPO: u32 = 500_000;
+
+impl TempoMap {
+    /// Builds a `TempoMap` from the `Tempo` events of all passed tracks. SMPTE timecode files ignore tempo events, as the ticks are already real time.
+    pub fn from_midi_tracks(tracks: &[MidiTrack], timing: &Timing) -> Self {
+        let ticks_per_beat = match timing {
+            Timing::Metrical(t) => t.as_int().max(1),
+            Timing::Timecode(fps, subframe) => {
+                let micros_per_tick =
+                    1_000_000f64 / (fps.as_f32() as f64 * *subframe.max(&1) as f64);
+                return Self {
+                    segments: vec![TempoSegment {
+                        tick: 0,
+                        micros: 0f64,
+                        micros_per_tick,
+                    }],
+                    reference: micros_per_tick,
+                    ticks_per_beat: None,
+                };
+            }
+        };
+
+        // Collect (tick, microseconds per beat) from all tracks
+        let mut tempos = Vec::<(u64, u32)>::new();
+        for track in tracks {
+            let mut current_time = 0u64;
+            for event in track {
+                current_time += u64::from(u32::from(event.delta));
+                if let TrackEventKind::Meta(MetaMessage::Tempo(t)) = event.kind {
+                    tempos.push((current_time, t.as_int()));
+                }
+            }
+        }
+        // Stable sort, so the last tempo event at a tick wins when building the segments
+        tempos.sort_by_key(|t| t.0);
+
+        let mut segments = vec![TempoSegment {
+            tick: 0,
+            micros: 0f64,
+            micros_per_tick: DEFAULT_TEMPO as f64 / ticks_per_beat as f64,
+        }];
+
+        for (tick, tempo) in tempos {
+            let micros_per_tick = tempo as f64 / ticks_per_beat as f64;
+            let last = *segments.last().unwrap();
+            if last.tick == tick {
+                segments.last_mut().unwrap().micros_per_tick = micros_per_tick;
+                continue;
+            }
+            segments.push(TempoSegment {
+                tick,
+                micros: last.micros + (tick - last.tick) as f64 * last.micros_per_tick,
+                micros_per_tick,
+            });
+        }
+
+        Self {
+            reference: segments[0].micros_per_tick,
+            segments,
+            ticks_per_beat: Some(ticks_per_beat),
+        }
+    }
+
+    /// Returns the time in microseconds at which a MidiTick occurs
+    pub fn micros(&self, tick: u64) -> f64 {
+        let segment = self.segment_by_tick(tick);
+        segment.micros + (tick - segment.tick) as f64 * segment.micros_per_tick
+    }
+
+    /// Converts a MidiTick into a tempo-adjusted tick
+    pub fn adjust(&self, tick: u64) -> u64 {
+        (self.micros(tick) / self.reference).round() as u64
+    }
+
+    /// Converts a tempo-adjusted tick back into a MidiTick
+    pub fn unadjust(&self, abs: u64) -> u64 {
+        let micros = abs as f64 * self.reference;
+        let segment = self
+            .segments
+            .iter()
+            .rev()
+            .find(|t| t.micros <= micros)
+            .unwrap_or(&self.segments[0]);
+        segment.tick + ((micros - segment.micros) / segment.micros_per_tick).round() as u64
+    }
+
+    /// Returns the time in seconds of a tempo-adjusted tick
+    pub fn seconds(&self, abs: u64) -> f64 {
+        abs as f64 * self.reference / 1_000_000f64
+    }
+
+    /// The length of a tempo-adjusted tick in microseconds
+    pub fn reference(&self) -> f64 {
+        self.reference
+    }
+
+    /// The ticks per beat if the file uses metrical timing
+    pub fn ticks_per_beat(&self) -> Option<u16> {
+        self.ticks_per_beat
+    }
+
+    /// Returns the segment a MidiTick is in
+    fn segment_by_tick(&self, tick: u64) -> &TempoSegment {
+        self.segments
+            .iter()
+            .rev()
+            .find(|t| t.tick <= tick)
+            .unwrap_or(&self.segments[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TempoMap;
+    use midly::{
+        num::{u15, u24, u28},
+        Fps, MetaMessage, Timing, TrackEvent, TrackEventKind,
+    };
+
+    fn tempo(delta: u32, micros_per_beat: u32) -> TrackEvent<'static> {
+        TrackEvent {
+            delta: u28::from(delta),
+            kind: TrackEventKind::Meta(MetaMessage::Tempo(u24::from(micros_per_beat))),
+        }
+    }
+
+    #[test]
+    fn ritardando() {
+        // 120 bpm for one beat, then 60 bpm
+        let conductor = vec![tempo(0, 500_000), tempo(480, 1_000_000)];
+        let map = TempoMap::from_midi_tracks(&[conductor], &Timing::Metrical(u15::from(480)));
+
+        assert_eq!(map.adjust(480), 480);
+        // The second beat takes twice as long
+        assert_eq!(map.adjust(960), 1440);
+        assert_eq!(map.unadjust(1440), 960);
+        assert_eq!(map.seconds(map.adjust(960)), 1.5);
+    }
+
+    #[test]
+    fn timecode() {
+        // 25 frames with 40 ticks each are 1000 ticks per second. Tempo events are ignored
+        let map = TempoMap::from_midi_tracks(
+            &[vec![tempo(0, 1_000_000)]],
+            &Timing::Timecode(Fps::Fps25, 40),
+        );
+
+        assert_eq!(map.adjust(1000), 1000);
+        assert_eq!(map.seconds(1000), 1.0);
+        assert_eq!(map.ticks_per_beat(), None);
+    }
+}
@@ -0,0 +1,26 @@
+// Modules
+pub mod functions;
+
+/// Maps MidiTicks onto real time. Built from the tempo events of all tracks and the timing of the midi header.
+///
+/// Tempo-adjusted ticks are ticks at the reference tempo, which is the tempo at tick 0. Laying notes out by tempo-adjusted ticks means the spacing on the strip follows the real time.
+#[derive(Debug, Clone)]
+pub struct TempoMap {
+    /// The tempo changes sorted by tick. There is always at least one starting at tick 0
+    segments: Vec<TempoSegment>,
+    /// Microseconds per tick of the reference tempo
+    reference: f64,
+    /// The ticks per beat if the file uses metrical timing. `None` for SMPTE timecode
+    ticks_per_beat: Option<u16>,
+}
+
+/// A part of the `TempoMap` with a constant tempo
+#[derive(Debug, Clone, Copy)]
+struct TempoSegment {
+    /// The tick the segment starts at
+    tick: u64,
+    /// The time in microseconds at which the segment starts
+    micros: f64,
+    /// The length of one tick in microseconds
+    micros_per_tick: f64,
+}
@@ -0,0 +1,140 @@
+// Internal
+use super::{Beat, TimeSignatureMap, TimeSignatureSegment};
+
+// midly
+use midly::{MetaMessage, Timing, Track as MidiTrack, TrackEventKind};
+
+impl TimeSignatureMap {
+    /// Builds a `TimeSignatureMap` from the `TimeSignature` events of all passed tracks. Files without any are in 4/4.
+    /// SMPTE timecode files have no beats, so `None` is returned for them.
+    pub fn from_midi_tracks(tracks: &[MidiTrack], timing: &Timing) -> Option<Self> {
+        let ticks_per_beat = match timing {
+            Timing::Metrical(t) => t.as_int().max(1),
+            Timing::Timecode(..) => return None,
+        };
+
+        // Collect (tick, numerator, denominator) from all tracks
+        let mut signatures = Vec::<(u64, u8, u8)>::new();
+        for track in tracks {
+            let mut current_time = 0u64;
+            for event in track {
+                current_time += u64::from(u32::from(event.delta));
+                if let TrackEventKind::Meta(MetaMessage::TimeSignature(
+                    numerator,
+                    denominator,
+                    ..,
+                )) = event.kind
+                {
+                    // A zero numerator would never advance to the next bar
+                    if numerator == 0 {
+                        continue;
+                    }
+                    signatures.push((current_time, numerator, denominator));
+                }
+            }
+        }
+        // Stable sort, so the last time signature event at a tick wins
+        signatures.sort_by_key(|t| t.0);
+
+        let mut segments = vec![TimeSignatureSegment {
+            tick: 0,
+            numerator: 4,
+            denominator: 2,
+        }];
+
+        for (tick, numerator, denominator) in signatures {
+            if segments.last().unwrap().tick == tick {
+                segments.pop();
+            }
+            segments.push(TimeSignatureSegment {
+                tick,
+                numerator,
+                denominator,
+            });
+        }
+
+        Some(Self {
+            segments,
+            ticks_per_beat,
+        })
+    }
+
+    /// Returns all beats up to and including the passed MidiTick
+    pub fn beats(&self, end_tick: u64) -> Vec<Beat> {
+        let mut beats = Vec::<Beat>::new();
+        let mut bar = 0u32;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            let segment_end = match self.segments.get(i + 1) {
+                Some(t) => t.tick,
+                None => end_tick + 1,
+            };
+            // A quarter note is ticks_per_beat long. Eighth notes are half as long, half notes twice as long and so on
+            let beat_length = (self.ticks_per_beat as f64 * 4f64
+                / 2f64.powi(segment.denominator as i32))
+            .max(1f64);
+
+            let mut beat = 0u64;
+            loop {
+                let tick = segment.tick + (beat as f64 * beat_length).round() as u64;
+                if tick >= segment_end || tick > end_tick {
+                    break;
+                }
+                let downbeat = beat.is_multiple_of(segment.numerator as u64);
+                if downbeat {
+                    bar += 1;
+                }
+                beats.push(Beat {
+                    tick,
+                    bar,
+                    downbeat,
+                });
+                beat += 1;
+            }
+        }
+
+        beats
+    }
+
+    /// Returns the number of the bar the passed MidiTick is in
+    pub fn bar(&self, tick: u64) -> u32 {
+        self.beats(tick).last().map_or(1, |t| t.bar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TimeSignatureMap;
+    use midly::{
+        num::{u15, u28},
+        MetaMessage, Timing, TrackEvent, TrackEventKind,
+    };
+
+    fn time_signature(delta: u32, numerator: u8, denominator: u8) -> TrackEvent<'static> {
+        TrackEvent {
+            delta: u28::from(delta),
+            kind: TrackEventKind::Meta(MetaMessage::TimeSignature(numerator, denominator, 24, 8)),
+        }
+    }
+
+    #[test]
+    fn beats() {
+        // One bar of 2/4, then 6/8
+        let conductor = vec![time_signature(0, 2, 2), time_signature(960, 6, 3)];
+        let map =
+            TimeSignatureMap::from_midi_tracks(&[conductor], &Timing::Metrical(u15::from(480)))
+                .unwrap();
+        let beats = map.beats(960 + 6 * 240);
+
+        let bars = beats
+            .iter()
+            .filter(|t| t.downbeat)
+            .map(|t| (t.tick, t.bar))
+            .collect::<Vec<(u64, u32)>>();
+        assert_eq!(bars, vec![(0, 1), (960, 2), (2400, 3)]);
+        // Two quarters, six eighths and the downbeat of bar 3
+        assert_eq!(beats.len(), 9);
+        assert_eq!(beats[3].tick, 1200);
+        assert_eq!(map.bar(2399), 2);
+    }
+}
@@ -0,0 +1,35 @@
+// Modules
+pub mod functions;
+
+/// The bars and beats of a midi file. Built from the time signature events of all tracks and the ticks per beat of the midi header.
+///
+/// Everything is in MidiTicks. Use the `TempoMap` to convert them into tempo-adjusted ticks.
+#[derive(Debug, Clone)]
+pub struct TimeSignatureMap {
+    /// The time signature changes sorted by tick. There is always at least one starting at tick 0
+    segments: Vec<TimeSignatureSegment>,
+    /// The ticks per quarter note
+    ticks_per_beat: u16,
+}
+
+/// A part of the `TimeSignatureMap` with a constant time signature
+#[derive(Debug, Clone, Copy)]
+struct TimeSignatureSegment {
+    /// The tick the segment starts at. A new bar always starts here
+    tick: u64,
+    /// The beats per bar
+    numerator: u8,
+    /// The length of a beat as a power of two, e.g. 2 => quarter notes, 3 => eighth notes
+    denominator: u8,
+}
+
+/// A beat of a bar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Beat {
+    /// The MidiTick the beat starts at
+    pub tick: u64,
+    /// The number of the bar the beat is in. The first bar is bar 1
+    pub bar: u32,
+    /// Whether this is the first beat of the bar
+    pub downbeat: bool,
+}
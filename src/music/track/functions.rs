@@ -1,6 +1,6 @@
 // Internal
-use super::Track;
-use crate::music::{event::Event, music_box::MusicBox, note::Note};
+use super::{Resolution, Track};
+use crate::music::{event::Event, music_box::MusicBox, note::Note, tempo_map::TempoMap};
 use crate::prelude::*;
 
 use midly::num::{u28, u4, u7};
@@ -8,8 +8,18 @@ use midly::num::{u28, u4, u7};
 use midly::{MetaMessage, MidiMessage, Timing, Track as MidiTrack, TrackEvent};
 
 impl Track {
-    /// Converts a `MidiTrack` into a `Track`. Removes unplayable notes by the passed `MusicBox` and transposes them by octaves if `transpose` is set
-    pub fn from_midi_track(track: MidiTrack, music_box: &MusicBox, transpose: &bool) -> Self {
+    /// Converts one or more `MidiTrack`s into a single time-ordered `Track`. Only note events on the passed `channels` are used, all channels if `None`.
+    /// The absolute time of the events is converted into tempo-adjusted ticks by the passed `TempoMap`.
+    /// All notes are shifted by `shift` semitones first, see [Track::best_transposition].
+    /// Removes unplayable notes by the passed `MusicBox` and transposes them by octaves if `transpose` is set
+    pub fn from_midi_tracks(
+        tracks: &[MidiTrack],
+        channels: Option<&[u8]>,
+        tempo_map: &TempoMap,
+        music_box: &MusicBox,
+        transpose: &bool,
+        shift: i8,
+    ) -> Self {
         let mut output = Self {
             inner: Vec::<Event>::new(),
             tick_length: u64::MIN,
@@ -21,36 +31,23 @@ impl Track {
             "Transposing {}",
             if *transpose { "enabled" } else { "disabled" }
         );
-        // Current time used for assigning the absolute time value for each Event
-        let mut current_time = 0u64;
+        // Merge the note on events of all tracks into one timeline
+        let (merged, tick_length) = Self::merge_midi_tracks(tracks, channels);
         // Array used for calculating the min and max distance
-        // 127 is the number of midi pitches there are
-        let mut last_seen = [Option::None; 127];
-        // The outer loop over all `TrackEvents`
-        for event in track {
-            current_time += u64::from(u32::from(event.delta));
-
-            // Get the pitch and velocity of the event or continue if something else
-            let (mut pitch, vel) = match event.kind {
-                midly::TrackEventKind::Midi {
-                    message: MidiMessage::NoteOn { key, vel },
-                    ..
-                } => (key, vel),
-                // Some midi program send velocity 0 NoteOn events instead of NoteOff events. Just use that here to differentiate between NoteOff and NoteOn
-                midly::TrackEventKind::Midi {
-                    message: MidiMessage::NoteOff { key, .. },
-                    ..
-                } => (key, u7::from(0)),
-                _ => continue,
+        // 128 is the number of midi pitches there are
+        let mut last_seen = [Option::None; 128];
+        // The outer loop over all merged note events
+        for (tick, mut pitch, vel) in merged {
+            let current_time = tempo_map.adjust(tick);
+            let note = Note::from_midi_pitch(pitch).shift(shift);
+            pitch = match note.to_midi_pitch() {
+                Some(t) => t,
+                None => {
+                    warn!("Note '{note}' at '{current_time}' with velocity '{vel}' is outside of the midi range after shifting. Skipping.");
+                    continue;
+                }
             };
 
-            // Continue if it is a note off. Don't waste time calculating stuff for a note that will be discarded
-            if vel == 0 {
-                continue;
-            }
-
-            let note = Note::from_midi_pitch(pitch);
-
             if !music_box.is_valid_note(&note) {
                 // Note can't be played
                 match transpose {
@@ -67,14 +64,17 @@ impl Track {
                     true => {
                         let note_octave = note.get_octave();
                         let mut transposable = false;
-                        // This should cover the whole midi pitch spectrum. My converted notes go far beyond this so it doesn't matter if the notes have negative hz or are over 20khz
                         for transpose_octave in -1..=9 {
                             if !music_box.is_valid_note(&note.transpose(transpose_octave)) {
                                 continue;
                             }
                             // Could transpose.
                             info!("Transposing note '{note}' at '{current_time}' with velocity '{vel}' from octave '{note_octave}' to '{transpose_octave}'");
-                            pitch = note.transpose(transpose_octave).to_midi_pitch();
+                            pitch = match note.transpose(transpose_octave).to_midi_pitch() {
+                                Some(t) => t,
+                                None => continue,
+                            };
+                            transposable = true;
                             break;
                         }
                         if !transposable {
@@ -109,9 +109,7 @@ impl Track {
             }
 
             // Saving that a note has been encountered
-            if vel != 0 {
-                last_seen[u8::from(pitch) as usize] = Some(current_time);
-            }
+            last_seen[u8::from(pitch) as usize] = Some(current_time);
 
             // Add to track
             output.inner.push(Event::new(
@@ -122,11 +120,130 @@ impl Track {
         }
 
         // This is the total length in MidiTicks
-        output.tick_length = current_time;
+        output.tick_length = tempo_map.adjust(tick_length);
         output
     }
 
+    /// Tries every shift from `-range` to `range` semitones on the note events of the passed tracks and returns the one that fits the `MusicBox` best.
+    /// A shift is better if more notes are playable. If that is the same, the one keeping more intervals between successive notes wins, as it breaks the melody less.
+    /// For that the notes that aren't playable are folded into the lowest octave the music box has, like transposing does. An interval is kept if both notes are folded by the same number of octaves. After that the smaller shift wins.
+    pub fn best_transposition(
+        tracks: &[MidiTrack],
+        channels: Option<&[u8]>,
+        music_box: &MusicBox,
+        range: u8,
+    ) -> i8 {
+        let (merged, _) = Self::merge_midi_tracks(tracks, channels);
+        let notes = merged
+            .iter()
+            .map(|t| Note::from_midi_pitch(t.1))
+            .collect::<Vec<Note>>();
+
+        // (shift, playable notes, kept intervals)
+        let mut candidates = Vec::<(i8, usize, usize)>::new();
+        let range = range.min(i8::MAX as u8) as i8;
+
+        for shift in -range..=range {
+            // The octaves every note is moved by to be playable. `None` if it can't be played in any octave
+            let folds = notes
+                .iter()
+                .map(|t| {
+                    let note = t.shift(shift);
+                    if music_box.is_valid_note(&note) {
+                        return Some(0i8);
+                    }
+                    (-1..=9i8)
+                        .find(|el| music_box.is_valid_note(&note.transpose(*el)))
+                        .map(|el| el - *note.get_octave())
+                })
+                .collect::<Vec<Option<i8>>>();
+
+            candidates.push((
+                shift,
+                folds.iter().filter(|t| **t == Some(0)).count(),
+                folds
+                    .windows(2)
+                    .filter(|t| t[0].is_some() && t[0] == t[1])
+                    .count(),
+            ));
+        }
+
+        candidates.sort_by(|a, b| {
+            b.1.cmp(&a.1)
+                .then(b.2.cmp(&a.2))
+                .then(a.0.abs().cmp(&b.0.abs()))
+                .then(b.0.cmp(&a.0))
+        });
+
+        let best = candidates[0];
+        info!(
+            "Transposing all notes by '{0}' semitone(s). {1} of {2} notes are playable, {3} intervals are kept when folding the rest into other octaves.",
+            best.0,
+            best.1,
+            notes.len(),
+            best.2
+        );
+        for candidate in candidates.iter().skip(1).take(3) {
+            info!(
+                "Runner-up: '{0}' semitone(s). {1} of {2} notes are playable, {3} intervals are kept when folding the rest into other octaves.",
+                candidate.0,
+                candidate.1,
+                notes.len(),
+                candidate.2
+            );
+        }
+
+        best.0
+    }
+
+    /// Collects the note on events of all `tracks` into one list ordered by their absolute time. Events at the same time keep the order of the tracks.
+    /// Returns the list of `(absolute time, pitch, velocity)` and the length of the longest track in MidiTicks.
+    fn merge_midi_tracks(
+        tracks: &[MidiTrack],
+        channels: Option<&[u8]>,
+    ) -> (Vec<(u64, u7, u7)>, u64) {
+        let mut merged = Vec::<(u64, u7, u7)>::new();
+        let mut tick_length = u64::MIN;
+
+        for track in tracks {
+            // Current time used for assigning the absolute time value for each event
+            let mut current_time = 0u64;
+            for event in track {
+                current_time += u64::from(u32::from(event.delta));
+
+                // Get the channel, pitch and velocity of the event or continue if something else
+                let (channel, pitch, vel) = match event.kind {
+                    midly::TrackEventKind::Midi {
+                        channel,
+                        message: MidiMessage::NoteOn { key, vel },
+                    } => (channel, key, vel),
+                    _ => continue,
+                };
+
+                // Some midi program send velocity 0 NoteOn events instead of NoteOff events. These are discarded as well
+                if vel == 0 {
+                    continue;
+                }
+
+                if let Some(t) = channels {
+                    if !t.contains(&channel.as_int()) {
+                        continue;
+                    }
+                }
+
+                merged.push((current_time, pitch, vel));
+            }
+            tick_length = std::cmp::max(current_time, tick_length);
+        }
+
+        // Stable sort, so notes at the same time stay in track order
+        merged.sort_by_key(|t| t.0);
+
+        (merged, tick_length)
+    }
+
     /// Converts a `Track` into a `MidiTrack`. Copies the Midi meta events from the passed track.
+    /// The events are in tempo-adjusted ticks, so the copied tempo should be the tempo at tick 0.
     pub fn to_midi_track<'a>(&self, track: MidiTrack<'a>) -> MidiTrack<'a> {
         // Create new track
         let mut output = MidiTrack::default();
@@ -175,12 +292,19 @@ impl Track {
         // Convert from absolute to delta and push it onto the track
         let mut prev_abs = 0;
         for event in self.inner.clone() {
+            let key = match event.note.to_midi_pitch() {
+                Some(t) => t,
+                None => {
+                    warn!("Note '{}' at '{}' is outside of the midi range. Not writing it to the midi file.", event.note, event.abs);
+                    continue;
+                }
+            };
             output.push(TrackEvent {
                 delta: u28::from((event.abs - prev_abs) as u32),
                 kind: midly::TrackEventKind::Midi {
                     channel: u4::from(0),
                     message: MidiMessage::NoteOn {
-                        key: event.note.to_midi_pitch(),
+                        key,
                         vel: u7::from(event.vel),
                     },
                 },
@@ -197,7 +321,200 @@ impl Track {
         output
     }
 
-    /// The length in MidiTicks
+    /// Returns the indices of all pairs of events with the same note which are closer than `min_distance` ticks.
+    /// Overlapping notes are ignored like they are in the distance calculation.
+    pub fn same_note_conflicts(&self, min_distance: u64) -> Vec<(usize, usize)> {
+        let mut conflicts = Vec::<(usize, usize)>::new();
+        // 128 is the number of midi pitches there are
+        let mut last_seen: [Option<usize>; 128] = [Option::None; 128];
+
+        for (i, event) in self.inner.iter().enumerate() {
+            let pitch = match event.note.to_midi_pitch() {
+                Some(t) => t.as_int() as usize,
+                None => continue,
+            };
+            if let Some(t) = last_seen[pitch] {
+                let distance = event.abs - self.inner[t].abs;
+                if distance != 0 && distance < min_distance {
+                    conflicts.push((t, i));
+                }
+            }
+            last_seen[pitch] = Some(i);
+        }
+
+        conflicts
+    }
+
+    /// Makes every pair of notes on the same tooth that are closer than `min_distance` ticks playable instead of stretching the strip.
+    /// The strategies are tried in order on the later note of a pair. Pairs none of them can resolve are left as they are.
+    /// A note is nudged by `nudge` ticks at most. The later note is nudged forward, or the earlier one back if there is no room after the later one.
+    /// Updates the minimum and maximum distance. Returns how many pairs were resolved
+    pub fn resolve_conflicts(
+        &mut self,
+        min_distance: u64,
+        strategies: &[Resolution],
+        music_box: &MusicBox,
+        nudge: u64,
+    ) -> usize {
+        // The times of all notes on every tooth before resolving. 128 is the number of midi pitches there are
+        let mut teeth = vec![Vec::<u64>::new(); 128];
+        for event in self.inner.iter().filter(|el| el.vel != 0) {
+            if let Some(t) = event.note.to_midi_pitch() {
+                teeth[t.as_int() as usize].push(event.abs);
+            }
+        }
+        // The time of the first note on the tooth at or after the passed time
+        let next = |pitch: usize, abs: u64| {
+            let tooth = &teeth[pitch];
+            tooth.get(tooth.partition_point(|el| *el < abs)).copied()
+        };
+        // The time of the next note on the same tooth for every event
+        let mut following = vec![Option::<u64>::None; self.inner.len()];
+        let mut next_seen: [Option<u64>; 128] = [Option::None; 128];
+        for (i, event) in self.inner.iter().enumerate().rev() {
+            if let Some(t) = event.note.to_midi_pitch().filter(|_| event.vel != 0) {
+                following[i] = next_seen[t.as_int() as usize];
+                next_seen[t.as_int() as usize] = Some(event.abs);
+            }
+        }
+
+        let mut output = Vec::<Event>::with_capacity(self.inner.len());
+        // The index in output of the last note on every tooth and the time of the note before it
+        let mut last: [Option<usize>; 128] = [Option::None; 128];
+        let mut before_last: [Option<u64>; 128] = [Option::None; 128];
+        let mut resolved = 0usize;
+
+        for (i, mut event) in std::mem::take(&mut self.inner).into_iter().enumerate() {
+            let mut pitch = match event.note.to_midi_pitch() {
+                Some(t) if event.vel != 0 => t.as_int() as usize,
+                _ => {
+                    output.push(event);
+                    continue;
+                }
+            };
+
+            let previous = match last[pitch].map(|t| output[t].abs) {
+                Some(t) if event.abs > t && event.abs - t < min_distance => Some(t),
+                _ => None,
+            };
+            // The strategy that worked for the conflict with the previous note
+            let mut resolution = None::<Resolution>;
+            if let Some(previous) = previous {
+                for strategy in strategies {
+                    match strategy {
+                        Resolution::Drop => {
+                            info!(
+                                "Dropping note '{}' at '{}'. It's too close to the note before it.",
+                                event.note, event.abs
+                            );
+                        }
+                        Resolution::Octave => {
+                            let octave = *event.note.get_octave();
+                            // Closer octaves first, lower ones before higher ones
+                            let moved = (1..=10i8)
+                                .flat_map(|el| [octave - el, octave + el])
+                                .map(|el| event.note.transpose(el))
+                                .filter(|el| music_box.is_valid_note(el))
+                                .find_map(|el| {
+                                    let t = el.to_midi_pitch()?.as_int() as usize;
+                                    let free_before = last[t]
+                                        .is_none_or(|i| event.abs >= output[i].abs + min_distance);
+                                    let free_after = next(t, event.abs)
+                                        .is_none_or(|i| i >= event.abs + min_distance);
+                                    (free_before && free_after).then_some((el, t))
+                                });
+                            match moved {
+                                Some((note, t)) => {
+                                    info!("Moving note '{}' at '{}' to '{note}'. It's too close to the note before it.", event.note, event.abs);
+                                    event.note = note;
+                                    pitch = t;
+                                }
+                                None => continue,
+                            }
+                        }
+                        Resolution::Nudge => {
+                            let abs = previous + min_distance;
+                            if abs - event.abs > nudge {
+                                continue;
+                            }
+                            if following[i].is_none_or(|t| t >= abs + min_distance) {
+                                info!("Nudging note '{}' from '{}' to '{abs}'. It's too close to the note before it.", event.note, event.abs);
+                                event.abs = abs;
+                            } else {
+                                // No room after it, so the note before it is moved back instead
+                                let earlier = match event.abs.checked_sub(min_distance) {
+                                    Some(t)
+                                        if before_last[pitch]
+                                            .is_none_or(|el| t >= el + min_distance) =>
+                                    {
+                                        t
+                                    }
+                                    _ => continue,
+                                };
+                                info!("Nudging note '{}' from '{previous}' to '{earlier}'. It's too close to the note after it.", event.note);
+                                output[last[pitch].unwrap()].abs = earlier;
+                            }
+                        }
+                        Resolution::Merge => {
+                            let abs = previous + (event.abs - previous) / 2;
+                            if before_last[pitch].is_some_and(|t| abs < t + min_distance)
+                                || following[i].is_some_and(|t| t < abs + min_distance)
+                            {
+                                continue;
+                            }
+                            info!("Merging note '{}' at '{}' with the note at '{previous}' into one at '{abs}'.", event.note, event.abs);
+                            let merged = &mut output[last[pitch].unwrap()];
+                            merged.abs = abs;
+                            merged.vel = merged.vel.max(event.vel);
+                        }
+                    }
+                    resolution = Some(*strategy);
+                    break;
+                }
+            }
+
+            if resolution.is_some() {
+                resolved += 1;
+            }
+            // Dropped and merged notes are gone
+            if !matches!(resolution, Some(Resolution::Drop | Resolution::Merge)) {
+                before_last[pitch] = last[pitch].map(|t| output[t].abs);
+                last[pitch] = Some(output.len());
+                output.push(event);
+            }
+        }
+
+        // Nudged and merged notes have moved
+        output.sort_by_key(|el| el.abs);
+        self.inner = output;
+        self.update_distances();
+        resolved
+    }
+
+    /// Recalculates the minimum and maximum distance between two notes of the same key. Overlapping notes are ignored like when reading the midi file
+    fn update_distances(&mut self) {
+        self.min_distance = u64::MAX;
+        self.max_distance = u64::MIN;
+        // 128 is the number of midi pitches there are
+        let mut last_seen: [Option<u64>; 128] = [Option::None; 128];
+
+        for event in self.inner.iter().filter(|el| el.vel != 0) {
+            let pitch = match event.note.to_midi_pitch() {
+                Some(t) => t.as_int() as usize,
+                None => continue,
+            };
+            if let Some(t) = last_seen[pitch] {
+                let distance = event.abs - t;
+                if distance != 0 {
+                    self.min_distance = self.min_distance.min(distance);
+                    self.max_distance = self.max_distance.max(distance);
+                }
+            }
+            last_seen[pitch] = Some(event.abs);
+        }
+    }
+
+    /// The length in tempo-adjusted MidiTicks
     pub fn tick_length(&self) -> u64 {
         self.tick_length
     }
@@ -226,3 +543,151 @@ impl std::ops::DerefMut for Track {
         &mut self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Resolution, Track};
+    use crate::music::{event::Event, music_box::MusicBox, note::Note};
+    use midly::{
+        num::{u28, u4, u7},
+        MidiMessage, TrackEvent, TrackEventKind,
+    };
+
+    fn note_on(delta: u32, channel: u8, key: u8) -> TrackEvent<'static> {
+        TrackEvent {
+            delta: u28::from(delta),
+            kind: TrackEventKind::Midi {
+                channel: u4::from(channel),
+                message: MidiMessage::NoteOn {
+                    key: u7::from(key),
+                    vel: u7::from(64),
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn merge_tracks_and_channels() {
+        let first = vec![note_on(0, 0, 60), note_on(20, 1, 62), note_on(20, 0, 64)];
+        let second = vec![note_on(10, 3, 65), note_on(30, 0, 67)];
+
+        let (merged, tick_length) =
+            Track::merge_midi_tracks(&[first.clone(), second.clone()], None);
+        let keys = merged
+            .iter()
+            .map(|t| (t.0, t.1.as_int()))
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec![(0, 60), (10, 65), (20, 62), (40, 64), (40, 67)]);
+        assert_eq!(tick_length, 40);
+
+        let (merged, _) = Track::merge_midi_tracks(&[first, second], Some(&[0, 3]));
+        let keys = merged
+            .iter()
+            .map(|t| (t.0, t.1.as_int()))
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec![(0, 60), (10, 65), (40, 64), (40, 67)]);
+    }
+
+    #[test]
+    fn best_transposition() {
+        let music_box = MusicBox::new(
+            "test".to_string(),
+            10f64,
+            5f64,
+            None,
+            vec![Note::C(4), Note::D(4), Note::E(4), Note::G(4)],
+            None,
+        );
+        // A♯3, C4, D4, F4 fits the music box when shifted up by two semitones
+        let track = vec![
+            note_on(0, 0, 58),
+            note_on(10, 0, 60),
+            note_on(10, 0, 62),
+            note_on(10, 0, 65),
+        ];
+
+        assert_eq!(Track::best_transposition(&[track], None, &music_box, 24), 2);
+
+        // G5, F5, F4, D♯4 has two playable notes when shifted down by one or by three semitones.
+        // Down by three, E5 and D5 both fold one octave lower and keep their interval. Down by one, F♯5 can't be played in any octave
+        let track = vec![
+            note_on(0, 0, 79),
+            note_on(10, 0, 77),
+            note_on(10, 0, 65),
+            note_on(10, 0, 63),
+        ];
+        assert_eq!(
+            Track::best_transposition(&[track], None, &music_box, 24),
+            -3
+        );
+    }
+
+    #[test]
+    fn resolve_conflicts() {
+        let music_box = MusicBox::new(
+            "test".to_string(),
+            10f64,
+            5f64,
+            None,
+            vec![Note::C(4), Note::D(4), Note::C(5)],
+            None,
+        );
+        // The second C4 is 100 ticks after the first, the music box needs 300
+        let resolve_at = |ticks: [u64; 3], strategies: &[Resolution], nudge: u64| {
+            let mut track = Track::new();
+            for abs in ticks {
+                track.push(Event::new(Note::C(4), abs, 64));
+            }
+            let resolved = track.resolve_conflicts(300, strategies, &music_box, nudge);
+            let notes = track
+                .iter()
+                .map(|el| (el.note.to_string(), el.abs))
+                .collect::<Vec<(String, u64)>>();
+            (resolved, notes, track.min_distance())
+        };
+        let resolve = |strategies: &[Resolution], nudge: u64| {
+            let (resolved, notes, _) = resolve_at([0, 100, 800], strategies, nudge);
+            (resolved, notes)
+        };
+        let notes = |notes: &[(&str, u64)]| {
+            notes
+                .iter()
+                .map(|el| (el.0.to_string(), el.1))
+                .collect::<Vec<(String, u64)>>()
+        };
+
+        assert_eq!(
+            resolve(&[Resolution::Drop], 0),
+            (1, notes(&[("C4", 0), ("C4", 800)]))
+        );
+        assert_eq!(
+            resolve(&[Resolution::Octave], 0),
+            (1, notes(&[("C4", 0), ("C5", 100), ("C4", 800)]))
+        );
+        assert_eq!(
+            resolve(&[Resolution::Merge], 0),
+            (1, notes(&[("C4", 50), ("C4", 800)]))
+        );
+        // Too far to nudge, so it's dropped
+        assert_eq!(
+            resolve(&[Resolution::Nudge, Resolution::Drop], 100),
+            (1, notes(&[("C4", 0), ("C4", 800)]))
+        );
+        assert_eq!(
+            resolve(&[Resolution::Nudge, Resolution::Drop], 200),
+            (1, notes(&[("C4", 0), ("C4", 300), ("C4", 800)]))
+        );
+        // The note after it leaves no room, so the note before it is nudged back
+        assert_eq!(
+            resolve_at([400, 500, 800], &[Resolution::Nudge], 200),
+            (1, notes(&[("C4", 200), ("C4", 500), ("C4", 800)]), 300)
+        );
+        // The minimum distance is updated
+        assert_eq!(resolve_at([0, 100, 800], &[Resolution::Drop], 0).2, 800);
+        // Nothing resolves it
+        assert_eq!(
+            resolve(&[Resolution::Nudge], 0),
+            (0, notes(&[("C4", 0), ("C4", 100), ("C4", 800)]))
+        );
+    }
+}
@@ -18,6 +18,19 @@ pub struct Track {
     max_distance: u64,
 }
 
+/// How a note that is too close to the note before it on the same tooth is made playable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Leaves the later note out
+    Drop,
+    /// Moves the later note to the closest other octave the music box has
+    Octave,
+    /// Moves the later note forward, or the earlier note back, until they're far enough apart
+    Nudge,
+    /// Replaces both notes with one in the middle between them
+    Merge,
+}
+
 impl Track {
     pub fn new() -> Self {
         Self {
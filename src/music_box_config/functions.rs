@@ -102,21 +102,17 @@ impl MusicBoxConfig {
                                 self.open_file = Some(self.path_buf.clone());
                                 self.popup = true;
                             }
-                            KeyCode::Char('e') => {
-                                if self.index != 0 {
-                                    if self.update_settings_index(true).is_err() {
-                                        continue;
-                                    }
-                                    self.index -= 1;
+                            KeyCode::Char('e') if self.index != 0 => {
+                                if self.update_settings_index(true).is_err() {
+                                    continue;
                                 }
+                                self.index -= 1;
                             }
-                            KeyCode::Char('d') => {
-                                if self.index != self.max_index {
-                                    if self.update_settings_index(false).is_err() {
-                                        continue;
-                                    }
-                                    self.index += 1;
+                            KeyCode::Char('d') if self.index != self.max_index => {
+                                if self.update_settings_index(false).is_err() {
+                                    continue;
                                 }
+                                self.index += 1;
                             }
                             _ => (),
                         }
@@ -401,10 +397,7 @@ impl MusicBoxConfig {
         let parent = abs_path.parent();
 
         if let Some(t) = parent {
-            match std::fs::create_dir_all(t) {
-                Ok(t) => t,
-                Err(e) => (), /*return Err(Error::IOError(Box::new(e)))*/
-            }
+            std::fs::create_dir_all(t).unwrap_or_default(); // Errors are caught when creating the file
         }
 
         let mut file = std::fs::File::create(abs_path).to_res()?;
@@ -113,8 +113,8 @@ pub fn ui(f: &mut Frame, app: &mut MusicBoxConfig) {
     }
 
     // Save error popup
-    if app.save_error.is_some() {
-        let error = app.save_error.as_ref().unwrap().to_string();
+    if let Some(error) = &app.save_error {
+        let error = error.to_string();
         let block = Block::default()
             .title("Error")
             .borders(Borders::ALL)
@@ -142,8 +142,8 @@ pub fn ui(f: &mut Frame, app: &mut MusicBoxConfig) {
     }
 
     // Open error popup
-    if app.open_error.is_some() {
-        let error = app.open_error.as_ref().unwrap().to_string();
+    if let Some(error) = &app.open_error {
+        let error = error.to_string();
         let block = Block::default()
             .title(app.lang_map.val_at("capital.error"))
             .borders(Borders::ALL)
@@ -42,12 +42,23 @@ impl MusicBoxConvert {
         )
         .arg(
             Arg::new("io_box")
-                .help("Specifies which box file to use.")
+                .short('b')
+                .long("box")
+                .help("Specifies which box file to use. The built-in music boxes are used if the default file doesn't exist.")
                 .default_value("./box.json")
                 .num_args(1)
                 .value_name("FILE")
                 .required(false),
         )
+        .arg(
+            Arg::new("box_name")
+                .short('n')
+                .long("box-name")
+                .help("Specifies which music box from the box file to use. Uses the first one if not set.")
+                .num_args(1)
+                .value_name("NAME")
+                .required(false),
+        )
         .arg(
             Arg::new("io_out_midi")
                 .short('O')
@@ -57,17 +68,90 @@ impl MusicBoxConvert {
                 .value_name("FILE")
                 .required(false),
         )
+        .arg(
+            Arg::new("io_out_audio")
+                .long("audio-out")
+                .help("When set outputs a wav file of how the strip sounds on the music box. Notes the music box can't play are left out. Uses the crank rate if the box file has 'mm_per_crank_turn', the timing of the midi file otherwise.")
+                .num_args(1)
+                .value_name("FILE")
+                .required(false),
+        )
+        .arg(
+            Arg::new("io_out_report")
+                .long("conflict-report")
+                .help("When set outputs a report of every pair of notes on the same tooth that are closer than the music box can play at the scale of the strip. With the time, bar and page of the notes.")
+                .num_args(1)
+                .value_name("FILE")
+                .required(false),
+        )
+        .arg(
+            Arg::new("io_out_report_json")
+                .long("conflict-report-json")
+                .help("Like conflict-report but outputs json.")
+                .num_args(1)
+                .value_name("FILE")
+                .required(false),
+        )
+        .arg(
+            Arg::new("title")
+                .long("title")
+                .help("The title printed in the page header. Defaults to the first track name or text in the midi file, or the name of the midi file.")
+                .num_args(1)
+                .value_name("TITLE")
+                .required(false),
+        )
+        .arg(
+            Arg::new("format")
+                .short('f')
+                .long("format")
+                .help("The output format. 'svg' writes one file per page, 'pdf' writes all pages into score.pdf, 'dxf' writes one file per page for laser cutters, 'gcode' writes one file per page for plotters and punching machines, 'png' writes one preview image per page.")
+                .default_value("svg")
+                .value_parser(["svg", "pdf", "dxf", "gcode", "png"])
+                .num_args(1)
+                .value_name("FORMAT")
+                .required(false),
+        )
+        .arg(
+            Arg::new("dpi")
+                .long("dpi")
+                .help("The resolution of png files in dots per inch.")
+                .default_value("150")
+                .value_parser(value_parser!(f64))
+                .num_args(1)
+                .value_name("DPI")
+                .required(false),
+        )
+        .arg(
+            Arg::new("engrave")
+                .long("engrave")
+                .help("Adds the staff lines to an ENGRAVE layer in dxf files. Nothing else is engraved, the bounding box, bar and beat lines, marks and text are left out.")
+                .num_args(0)
+                .action(ArgAction::SetTrue)
+                .required(false),
+        )
         .arg(
             Arg::new("track")
                 .short('T')
                 .long("track")
-                .help("Specifies which track from the midi file to use. Zero-based.")
+                .help("Specifies which tracks from the midi file to use. Zero-based. Multiple tracks are seperated by commas and merged into one.")
                 .default_value("0")
                 .value_parser(value_parser!(usize))
+                .value_delimiter(',')
                 .num_args(1)
                 .value_name("TRACK_NUMBER")
                 .required(false),
         )
+        .arg(
+            Arg::new("channel")
+                .short('c')
+                .long("channel")
+                .help("Specifies which midi channels to use. Zero-based. Multiple channels are seperated by commas. Uses all channels if not set.")
+                .value_parser(value_parser!(u8).range(0..16))
+                .value_delimiter(',')
+                .num_args(1)
+                .value_name("CHANNEL")
+                .required(false),
+        )
         .arg(
             Arg::new("transpose")
                 .short('t')
@@ -77,6 +161,73 @@ impl MusicBoxConvert {
                 .num_args(0)
                 .required(false),
         )
+        .arg(
+            Arg::new("auto_transpose")
+                .short('a')
+                .long("auto-transpose")
+                .help("Shifts all notes by the number of semitones that makes the most notes playable.")
+                .default_value("false")
+                .num_args(0)
+                .required(false),
+        )
+        .arg(
+            Arg::new("transpose_range")
+                .long("transpose-range")
+                .help("The largest shift in semitones tried by auto-transpose.")
+                .default_value("24")
+                .value_parser(value_parser!(u8))
+                .num_args(1)
+                .value_name("SEMITONES")
+                .required(false),
+        )
+        .arg(
+            Arg::new("crank_rate")
+                .long("crank-rate")
+                .help("Lays the strip out by crank speed instead of fitting it to the minimum note distance. The crank turns per minute. Needs 'mm_per_crank_turn' in the box file. [default: 60]")
+                .value_parser(value_parser!(f64))
+                .num_args(1)
+                .value_name("TURNS_PER_MINUTE")
+                .required(false),
+        )
+        .arg(
+            Arg::new("tempo")
+                .long("tempo")
+                .help("Lays the strip out by crank speed for this target tempo instead of the tempo of the midi file. In beats per minute.")
+                .value_parser(value_parser!(f64))
+                .num_args(1)
+                .value_name("BPM")
+                .required(false),
+        )
+        .arg(
+            Arg::new("mm_per_beat")
+                .long("mm-per-beat")
+                .help("Sets the length of one beat on the strip in millimetres instead of fitting it to the minimum note distance. Notes on the same tooth that end up too close are reported.")
+                .value_parser(value_parser!(f64))
+                .num_args(1)
+                .value_name("MM")
+                .conflicts_with_all(["crank_rate", "tempo"])
+                .required(false),
+        )
+        .arg(
+            Arg::new("resolve")
+                .long("resolve")
+                .help("Makes notes on the same tooth that are too close playable instead of leaving them. Only when laying the strip out by crank speed or with --mm-per-beat. The strategies are tried in order. 'drop' leaves the later note out, 'octave' moves it to the closest other octave the music box has, 'nudge' moves it later, or the note before it earlier, by up to the nudge tolerance, 'merge' replaces both notes with one in the middle. Multiple strategies are seperated by commas.")
+                .value_parser(["drop", "octave", "nudge", "merge"])
+                .value_delimiter(',')
+                .num_args(1)
+                .value_name("STRATEGY")
+                .required(false),
+        )
+        .arg(
+            Arg::new("nudge_tolerance")
+                .long("nudge-tolerance")
+                .help("How far the nudge strategy can move a note on the strip in millimetres.")
+                .default_value("2")
+                .value_parser(value_parser!(f64))
+                .num_args(1)
+                .value_name("MM")
+                .required(false),
+        )
         .arg(
             Arg::new("verbosity")
                 .short('v')
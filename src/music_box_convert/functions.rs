@@ -8,7 +8,7 @@ use std::{
 use simplelog::*;
 
 // midly
-use midly::{MidiMessage, Smf, Track as MidiTrack, TrackEvent, TrackEventKind};
+use midly::{MetaMessage, MidiMessage, Smf, Track as MidiTrack, TrackEvent, TrackEventKind};
 
 // serde
 use serde::{Serialize, Serializer};
@@ -16,19 +16,43 @@ use serde::{Serialize, Serializer};
 // Internal
 use super::MusicBoxConvert;
 use crate::{
+    conflict_report::{Conflict, ConflictReport},
+    dxf_writer::DxfWriter,
+    gcode_writer::GcodeWriter,
     music::{
         self,
         event::Event,
         music_box::MusicBox,
         note::Note,
-        track::{self, Track},
+        tempo_map::TempoMap,
+        time_signature::TimeSignatureMap,
+        track::{self, Resolution, Track},
     },
+    pdf_writer::PdfWriter,
+    png_writer::PngWriter,
     prelude::*,
     settings::Settings,
-    svg_writer::{circle::Circle, document::Document, line::Line},
+    svg_writer::{
+        circle::Circle,
+        document::{Document, Layer},
+        line::Line,
+        path::Path,
+        rect::Rect,
+        text::{Text, TextAnchor},
+    },
     vec2::Vec2,
+    wav_writer::WavWriter,
 };
 
+/// The crank turns per minute used if only a tempo is given
+const DEFAULT_CRANK_RATE: f64 = 60f64;
+
+/// The sample rate of the audio preview
+const AUDIO_SAMPLE_RATE: u32 = 44_100;
+
+/// The space between a note label and the start of its staff line
+const NOTE_LABEL_GAP_MM: f64 = 0.5f64;
+
 impl MusicBoxConvert {
     pub fn run_output_file(mut self) -> Result<()> {
         self.initiate_logger()?;
@@ -36,7 +60,9 @@ impl MusicBoxConvert {
         self.load_settings()?;
         self.get_abs()?;
         self.set_scale_factor()?;
+        self.write_audio()?;
         self.generate_svgs()?;
+        self.write_conflict_reports()?;
         self.write_documents()?;
 
         Ok(())
@@ -52,6 +78,16 @@ impl MusicBoxConvert {
         self.output_documents()
     }
 
+    pub fn run_output_png(mut self) -> Result<Vec<Vec<u8>>> {
+        self.initiate_logger()?;
+        self.load_music_box()?;
+        self.load_settings()?;
+        self.get_abs()?;
+        self.set_scale_factor()?;
+        self.generate_svgs()?;
+        self.output_pngs()
+    }
+
     /// Initiates the logger with the correct log level. The logger is static and so this musn't be called more than once
     fn initiate_logger(&mut self) -> Result<()> {
         let verbosity = self.args.get_count("verbosity");
@@ -88,23 +124,13 @@ impl MusicBoxConvert {
     }
 
     /// Deserializes ./box.json and assigns the MusicBox with the name given via arguments to the self.music_box.
+    /// Falls back to the built-in music boxes if the default box file doesn't exist or doesn't contain a box with that name.
     fn load_music_box(&mut self) -> Result<()> {
-        let file = match File::open(self.args.get_one::<String>("io_box").unwrap()) {
-            Ok(t) => t,
-            Err(e) => {
-                return Err(Error::IOError(
-                    Box::new(e),
-                    Box::new(self.args.get_one::<String>("io_box").unwrap().to_string()),
-                ))
-            }
-        };
-
-        let deserialized: MusicBox = match serde_json::from_reader(BufReader::new(file)) {
-            Ok(t) => t,
-            Err(e) => return Err(Error::SerdeJsonError(Box::new(e))),
-        };
-
-        self.music_box = Some(deserialized);
+        self.music_box = Some(MusicBox::load(
+            self.args.get_one::<String>("io_box").unwrap(),
+            self.args.get_one::<String>("box_name"),
+            self.args.value_source("io_box") != Some(clap::parser::ValueSource::DefaultValue),
+        )?);
         Ok(())
     }
 
@@ -129,10 +155,22 @@ impl MusicBoxConvert {
         Ok(())
     }
 
-    /// Stores an absolute representation of the midi data in self.track. Also output a midi file including all track plus an extra one containing the possibly transposed track.
+    /// Stores an absolute representation of the midi data of all selected tracks and channels in self.track. Also output a midi file including all track plus an extra one containing the possibly transposed track.
     fn get_abs(&mut self) -> Result<()> {
         let mut input = self.args.get_one::<String>("io_in").unwrap().to_owned();
-        let track_number = self.args.get_one::<usize>("track").unwrap().to_owned();
+        let mut track_numbers = self
+            .args
+            .get_many::<usize>("track")
+            .unwrap()
+            .copied()
+            .collect::<Vec<usize>>();
+        // Merging a track with itself would double all of its notes
+        track_numbers.sort_unstable();
+        track_numbers.dedup();
+        let channels = self
+            .args
+            .get_many::<u8>("channel")
+            .map(|t| t.copied().collect::<Vec<u8>>());
         let transpose = self.args.get_flag("transpose");
 
         if input.chars().collect::<Vec<char>>()[0] == ' ' {
@@ -149,24 +187,55 @@ impl MusicBoxConvert {
             Err(e) => return Err(Error::MidiError(Box::new(e))),
         };
 
-        if track_number > smf.tracks.len() - 1 {
-            return Err(Error::Generic(format!(
-                "File only contains {0} track(s). Track number {1} is out of bounds. Remember that the track number is zero-based: 0 => track number 1, 3 => track number 4",
-                smf.tracks.len(),
-                track_number
-            )));
+        let mut tracks = Vec::<MidiTrack>::new();
+        for track_number in track_numbers.iter() {
+            if *track_number > smf.tracks.len() - 1 {
+                return Err(Error::Generic(format!(
+                    "File only contains {0} track(s). Track number {1} is out of bounds. Remember that the track number is zero-based: 0 => track number 1, 3 => track number 4",
+                    smf.tracks.len(),
+                    track_number
+                )));
+            }
+            tracks.push(smf.tracks[*track_number].clone());
         }
 
-        self.track = Some(Track::from_midi_track(
-            smf.tracks[track_number].clone(),
+        // The tempo events are usually on the first track, which doesn't have to be one of the selected tracks
+        self.tempo_map = Some(TempoMap::from_midi_tracks(&smf.tracks, &smf.header.timing));
+        self.time_signatures = TimeSignatureMap::from_midi_tracks(&smf.tracks, &smf.header.timing);
+
+        // The title for the page header. Falls back to the name of the midi file
+        self.title = match self.args.get_one::<String>("title") {
+            Some(t) => Some(t.clone()),
+            None => Self::title_from_midi(&smf.tracks).or_else(|| {
+                std::path::Path::new(&input)
+                    .file_stem()
+                    .map(|t| t.to_string_lossy().into_owned())
+            }),
+        };
+
+        let shift = match self.args.get_flag("auto_transpose") {
+            true => Track::best_transposition(
+                &tracks,
+                channels.as_deref(),
+                self.music_box.res()?,
+                *self.args.get_one::<u8>("transpose_range").unwrap(),
+            ),
+            false => 0,
+        };
+
+        self.track = Some(Track::from_midi_tracks(
+            &tracks,
+            channels.as_deref(),
+            self.tempo_map.res()?,
             self.music_box.res()?,
             &transpose,
+            shift,
         ));
 
         if self.track.res()?.len() < 2 {
             return Err(Error::Generic(format!(
-                "Track {} contains fewer than two playable notes",
-                track_number
+                "Track(s) {:?} contain fewer than two playable notes",
+                track_numbers
             )));
         }
 
@@ -179,10 +248,7 @@ impl MusicBoxConvert {
             let parent = abs_path.parent();
 
             if let Some(t) = parent {
-                match std::fs::create_dir_all(t) {
-                    Ok(t) => t,
-                    Err(e) => (), /*return Err(Error::IOError(Box::new(e)))*/
-                }
+                std::fs::create_dir_all(t).unwrap_or_default(); // Errors are caught when creating the file
             }
 
             let mut file = match File::create(abs_path) {
@@ -209,10 +275,16 @@ impl MusicBoxConvert {
     fn set_scale_factor(&mut self) -> Result<()> {
         let music_box = self.music_box.res()?;
 
-        // X (Horizontal): How much do we have to scale the notes for it to be compatible with the music box
+        let crank_scale = self.crank_scale()?;
+        let beat_scale = self.beat_scale()?;
+
+        // X (Horizontal): How much do we have to scale the notes for it to be compatible with the music box. Or how far the strip moves per tick when cranking or at the given beat length
         // Y (Vertical): How much space there is between two lines
         let mut scale_factor = Vec2::<f64>::new(
-            music_box.min_note_distance_mm / self.track.res()?.min_distance() as f64,
+            match crank_scale.or(beat_scale) {
+                Some(t) => t,
+                None => music_box.min_note_distance_mm / self.track.res()?.min_distance() as f64,
+            },
             music_box.vertical_note_distance(),
         );
 
@@ -220,11 +292,287 @@ impl MusicBoxConvert {
             return Err(Error::Generic("Music box to large for paper size. Consider changing the paper size in the configurator.".to_string()));
         }
 
+        let settings = self.settings.res()?;
+        if settings.cut_line_enable {
+            if settings.sprocket_hole_enable
+                && settings.sprocket_hole_distance_staff_mm + settings.note_hole_radius_mm
+                    > settings.cut_line_distance_mm
+            {
+                warn!("The sprocket holes reach past the cut line. Consider changing the cut line distance in the configurator.");
+            }
+            if settings.cut_line_distance_mm > settings.staff_offset_mm {
+                warn!("The cut line is outside of the page. Consider changing the staff offset in the configurator.");
+            }
+        }
+
         self.scale = Option::Some(scale_factor);
 
+        if crank_scale.or(beat_scale).is_some() {
+            self.resolve_conflicts()?;
+            self.warn_conflicts()?;
+        } else if self.args.get_many::<String>("resolve").is_some() {
+            warn!("The strip is fitted to the closest notes on the same tooth, so there is nothing to resolve. Lay it out by crank speed with --crank-rate or --tempo or set the beat length with --mm-per-beat.");
+        }
+
+        Ok(())
+    }
+
+    /// Writes a wav file of how the strip sounds on the music box if the audio-out argument is set.
+    /// The notes are timed by their position on the strip and the crank rate, or by the midi file if the music box has no 'mm_per_crank_turn'.
+    fn write_audio(&self) -> Result<()> {
+        let path_string = match self.args.get_one::<String>("io_out_audio") {
+            Some(t) => t,
+            None => return Ok(()),
+        };
+        let abs_path = match crate::path::absolute_path(path_string) {
+            Ok(t) => t,
+            Err(e) => return Err(Error::IOError(Box::new(e), Box::new(path_string.clone()))),
+        };
+        if let Some(t) = abs_path.parent() {
+            std::fs::create_dir_all(t).unwrap_or_default(); // Errors are caught when creating the file
+        }
+
+        let music_box = self.music_box.res()?;
+        let track = self.track.res()?;
+        let first_abs = match track.iter().next() {
+            Some(t) => t.abs,
+            None => return Ok(()),
+        };
+
+        let mm_per_second = match music_box.mm_per_crank_turn {
+            Some(t) => {
+                let crank_rate = *self
+                    .args
+                    .get_one::<f64>("crank_rate")
+                    .unwrap_or(&DEFAULT_CRANK_RATE);
+                if crank_rate <= 0f64 {
+                    return Err(Error::Generic(
+                        "The crank rate has to be above zero.".to_string(),
+                    ));
+                }
+                Some(t * crank_rate / 60f64)
+            }
+            None => {
+                warn!("The music box has no 'mm_per_crank_turn'. The audio preview uses the timing of the midi file.");
+                None
+            }
+        };
+
+        let mut writer = WavWriter::new(AUDIO_SAMPLE_RATE);
+        for event in track.iter() {
+            if event.vel == 0 || !music_box.is_valid_note(&event.note) {
+                continue;
+            }
+            let pitch = match event.note.to_midi_pitch() {
+                Some(t) => t.as_int() as f64,
+                None => continue,
+            };
+            let seconds = match mm_per_second {
+                Some(t) => (event.abs - first_abs) as f64 * self.scale.res()?.x / t,
+                None => self.tempo_map.res()?.seconds(event.abs - first_abs),
+            };
+            writer.pluck(seconds, 440f64 * 2f64.powf((pitch - 69f64) / 12f64));
+        }
+
+        info!("Writing audio preview to '{}'", abs_path.to_string_lossy());
+        writer.save(&abs_path)
+    }
+
+    /// Returns the millimetres the strip moves per tempo-adjusted tick if the strip is laid out by crank speed, `None` if it is fitted to the minimum note distance.
+    fn crank_scale(&self) -> Result<Option<f64>> {
+        let crank_rate = self.args.get_one::<f64>("crank_rate");
+        let tempo = self.args.get_one::<f64>("tempo");
+
+        if crank_rate.is_none() && tempo.is_none() {
+            return Ok(None);
+        }
+
+        let mm_per_crank_turn = match self.music_box.res()?.mm_per_crank_turn {
+            Some(t) => t,
+            None => return Err(Error::Generic(format!("The music box '{}' has no 'mm_per_crank_turn'. Measure how far the strip moves during one turn of the crank and add mm_per_crank_turn to the box file to lay the strip out by crank speed.", self.music_box.res()?.name))),
+        };
+
+        let crank_rate = *crank_rate.unwrap_or(&DEFAULT_CRANK_RATE);
+        if crank_rate <= 0f64 {
+            return Err(Error::Generic(
+                "The crank rate has to be above zero.".to_string(),
+            ));
+        }
+        let mm_per_second = mm_per_crank_turn * crank_rate / 60f64;
+
+        let tempo_map = self.tempo_map.res()?;
+        let mut seconds_per_tick = tempo_map.reference() / 1_000_000f64;
+
+        if let Some(t) = tempo {
+            let ticks_per_beat = match tempo_map.ticks_per_beat() {
+                Some(t) => t,
+                None => return Err(Error::Generic("The midi file uses SMPTE timing, which has no beats. The tempo can't be changed.".to_string())),
+            };
+            if *t <= 0f64 {
+                return Err(Error::Generic(
+                    "The tempo has to be above zero.".to_string(),
+                ));
+            }
+            // One beat takes 60 / tempo seconds
+            seconds_per_tick = 60f64 / t / ticks_per_beat as f64;
+        }
+
+        Ok(Some(mm_per_second * seconds_per_tick))
+    }
+
+    /// Returns the millimetres the strip moves per tempo-adjusted tick if the length of a beat is given, `None` if the strip is fitted to the minimum note distance.
+    fn beat_scale(&self) -> Result<Option<f64>> {
+        let mm_per_beat = match self.args.get_one::<f64>("mm_per_beat") {
+            Some(t) => *t,
+            None => return Ok(None),
+        };
+        if mm_per_beat <= 0f64 {
+            return Err(Error::Generic(
+                "The millimetres per beat have to be above zero.".to_string(),
+            ));
+        }
+
+        match self.tempo_map.res()?.ticks_per_beat() {
+            Some(t) => Ok(Some(mm_per_beat / t as f64)),
+            None => Err(Error::Generic("The midi file uses SMPTE timing, which has no beats. Lay the strip out by crank speed instead.".to_string())),
+        }
+    }
+
+    /// Makes the notes on the same tooth that are closer than the music box can play playable with the strategies of the resolve argument.
+    fn resolve_conflicts(&mut self) -> Result<()> {
+        let strategies = match self.args.get_many::<String>("resolve") {
+            Some(t) => t
+                .map(|el| match el.as_str() {
+                    "drop" => Resolution::Drop,
+                    "octave" => Resolution::Octave,
+                    "nudge" => Resolution::Nudge,
+                    _ => Resolution::Merge,
+                })
+                .collect::<Vec<Resolution>>(),
+            None => return Ok(()),
+        };
+        let nudge = *self.args.get_one::<f64>("nudge_tolerance").unwrap();
+        if nudge < 0f64 {
+            return Err(Error::Generic(
+                "The nudge tolerance can't be negative.".to_string(),
+            ));
+        }
+
+        let music_box = self.music_box.res()?;
+        let scale_x = self.scale.res()?.x;
+        let resolved = self.track.res_mut()?.resolve_conflicts(
+            (music_box.min_note_distance_mm / scale_x).ceil() as u64,
+            &strategies,
+            music_box,
+            (nudge / scale_x).floor() as u64,
+        );
+        if resolved > 0 {
+            info!("Resolved {resolved} note(s) that were too close to the previous note on the same tooth.");
+        }
+
+        Ok(())
+    }
+
+    /// Warns about every pair of notes on the same tooth that are closer than the music box can play.
+    fn warn_conflicts(&self) -> Result<()> {
+        let music_box = self.music_box.res()?;
+        let track = self.track.res()?;
+        let scale_x = self.scale.res()?.x;
+
+        let conflicts =
+            track.same_note_conflicts((music_box.min_note_distance_mm / scale_x).ceil() as u64);
+
+        for (first, second) in conflicts.iter() {
+            warn!(
+                "Notes '{0}' at '{1}' and '{2}' are only {3:.2}mm apart. The music box needs at least {4}mm.",
+                track[*first].note,
+                track[*first].abs,
+                track[*second].abs,
+                (track[*second].abs - track[*first].abs) as f64 * scale_x,
+                music_box.min_note_distance_mm
+            );
+        }
+
+        if !conflicts.is_empty() {
+            warn!(
+                "Found {} note(s) that are too close to the previous note on the same tooth.",
+                conflicts.len()
+            );
+        }
+
         Ok(())
     }
 
+    /// Writes the conflict reports if the conflict-report or conflict-report-json arguments are set. Needs the pages, so it's called after generating the svgs
+    fn write_conflict_reports(&self) -> Result<()> {
+        let paths = [("io_out_report", false), ("io_out_report_json", true)]
+            .into_iter()
+            .filter_map(|(id, json)| Some((self.args.get_one::<String>(id)?, json)))
+            .collect::<Vec<(&String, bool)>>();
+        if paths.is_empty() {
+            return Ok(());
+        }
+
+        let report = self.conflict_report()?;
+        for (path_string, json) in paths {
+            let abs_path = match crate::path::absolute_path(path_string) {
+                Ok(t) => t,
+                Err(e) => return Err(Error::IOError(Box::new(e), Box::new(path_string.clone()))),
+            };
+            if let Some(t) = abs_path.parent() {
+                std::fs::create_dir_all(t).unwrap_or_default(); // Errors are caught when creating the file
+            }
+
+            info!(
+                "Writing conflict report to '{}'",
+                abs_path.to_string_lossy()
+            );
+            report.save(&abs_path, json)?;
+        }
+
+        Ok(())
+    }
+
+    /// Collects every pair of notes on the same tooth that are closer than the music box can play at the scale of the strip
+    fn conflict_report(&self) -> Result<ConflictReport> {
+        let music_box = self.music_box.res()?;
+        let track = self.track.res()?;
+        let tempo_map = self.tempo_map.res()?;
+        let scale_x = self.scale.res()?.x;
+
+        let conflicts = track
+            .same_note_conflicts((music_box.min_note_distance_mm / scale_x).ceil() as u64)
+            .into_iter()
+            .map(|(first, second)| {
+                let (first, second) = (&track[first], &track[second]);
+                Conflict {
+                    note: second.note.clone(),
+                    first_tick: first.abs,
+                    second_tick: second.abs,
+                    first_seconds: tempo_map.seconds(first.abs),
+                    second_seconds: tempo_map.seconds(second.abs),
+                    distance_mm: (second.abs - first.abs) as f64 * scale_x,
+                    bar: self
+                        .time_signatures
+                        .as_ref()
+                        .map(|t| t.bar(tempo_map.unadjust(second.abs))),
+                    page: self
+                        .page_starts
+                        .partition_point(|t| *t <= second.abs)
+                        .max(1),
+                }
+            })
+            .collect::<Vec<Conflict>>();
+
+        Ok(ConflictReport {
+            title: self.title.clone(),
+            music_box: music_box.name.clone(),
+            min_note_distance_mm: music_box.min_note_distance_mm,
+            mm_per_tick: scale_x,
+            conflicts,
+        })
+    }
+
     /// Generates the svgs and saves them in self.svg.
     fn generate_svgs(&mut self) -> Result<()> {
         // Pages
@@ -236,81 +584,204 @@ impl MusicBoxConvert {
         let mut overflow_notes = u64::MIN;
         let mut overflow_sprockets = 0f64;
 
+        // Row stacking
+        let rows_per_page = self.rows_per_page()?;
+        let mut row = 0usize;
+        // Leave room for the continuation marks after the end of a row
+        let chevron_room = match self.settings.res()?.continuation_mark_enable {
+            true => 2f64 * self.settings.res()?.continuation_mark_size_mm,
+            false => 0f64,
+        };
+
         for event in self.track.clone().unwrap().iter() {
             if event.vel == 0 {
                 continue;
             }
             if (event.abs - first_note_abs + overflow_notes) as f64 * self.scale.res()?.x
                 + self.settings.res()?.staff_offset_mm
+                + self.glue_width(pages.len() == 1)?
+                + chevron_room
                 > self.settings.res()?.paper_size_x
             {
-                overflow_sprockets =
-                    self.draw_page(pages.last().unwrap(), overflow_notes, overflow_sprockets)?;
+                overflow_sprockets = self.draw_page(
+                    pages.last().unwrap(),
+                    Self::previous_page(&pages),
+                    overflow_notes,
+                    overflow_sprockets,
+                    row,
+                    false,
+                )?;
+                row = (row + 1) % rows_per_page;
                 overflow_notes = event.abs - pages.last().unwrap().last().unwrap().abs;
                 pages.push(Vec::<Event>::new());
                 first_note_abs = event.abs;
             }
 
+            if row == 0 && pages.last().unwrap().is_empty() {
+                self.page_starts.push(event.abs);
+            }
             pages.last_mut().unwrap().push(event.clone());
         }
 
-        self.draw_page(pages.last().unwrap(), overflow_notes, overflow_sprockets)?;
+        self.draw_page(
+            pages.last().unwrap(),
+            Self::previous_page(&pages),
+            overflow_notes,
+            overflow_sprockets,
+            row,
+            true,
+        )?;
+
+        self.draw_headers()?;
 
         Ok(())
     }
 
+    /// Returns the first track name in the midi file, or the first text event if no track is named
+    fn title_from_midi(tracks: &[MidiTrack]) -> Option<String> {
+        let find = |name: bool| {
+            tracks.iter().flatten().find_map(|event| {
+                let text = match event.kind {
+                    TrackEventKind::Meta(MetaMessage::TrackName(t)) if name => t,
+                    TrackEventKind::Meta(MetaMessage::Text(t)) if !name => t,
+                    _ => return None,
+                };
+                let text = String::from_utf8_lossy(text).trim().to_string();
+                (!text.is_empty()).then_some(text)
+            })
+        };
+        find(true).or_else(|| find(false))
+    }
+
     /// Don't call manually.
     /// It's called by <code>self.generate_svgs</code>.
+    /// Draws one strip row. Row zero starts a new document, the other rows are stacked below the last one.
+    /// The end of the row before is repeated in the glue area at the start of the row.
     fn draw_page(
         &mut self,
         notes: &Vec<Event>,
+        glue_notes: &[Event],
         overflow_notes: u64,
         overflow_sprockets: f64,
+        row: usize,
+        last: bool,
     ) -> Result<f64> {
         // Output
-        let mut document = Document::default();
+        let first = self.svg.is_empty();
+        let mut document = match row {
+            0 => Document::new(
+                self.settings.res()?.paper_size_x,
+                self.settings.res()?.paper_size_y,
+            ),
+            _ => self.svg.pop().unwrap(),
+        };
+        // The zero-based number of the page the row is on
+        let page = self.svg.len();
+
+        // Vertical position of the top staff line of this row
+        let staff_y = self.staff_y(row)?;
+
+        // Horizontal position where the row continues the one before. Everything in front of it is the glue area
+        let glue = self.glue_width(first)?;
+        let origin_x = self.settings.res()?.staff_offset_mm + glue;
+
+        // The tempo-adjusted tick at origin_x. It's the last note of the row before
+        let start_abs = notes.first().unwrap().abs - overflow_notes;
+
+        // Horizontal position of the end of the staff
+        let end_x = (notes.last().unwrap().abs - start_abs) as f64 * self.scale.res()?.x + origin_x;
+
+        // Vertical positions of the highest and the lowest note line
+        let (staff_top, staff_bot) = self.music_box.res()?.note_y_range_mm();
+        let (staff_top, staff_bot) = (staff_y + staff_top, staff_y + staff_bot);
+
+        // Vertical positions of the top and bottom bounding box lines
+        let top_y = staff_top
+            - self
+                .settings
+                .res()?
+                .staff_bounding_box_top_bottom_distance_mm;
+        let bot_y = staff_bot
+            + self
+                .settings
+                .res()?
+                .staff_bounding_box_top_bottom_distance_mm;
 
         // Draw note lines
-        for i in 0..self.music_box.res()?.note_count() {
-            let current_pos =
-                self.settings.res()?.staff_offset_mm + (i as f64 * self.scale.res()?.y);
+        for i in (0..self.music_box.res()?.note_count()).rev() {
+            let current_pos = staff_y + self.music_box.res()?.note_y_mm(i);
             document.append(
+                Layer::Staff,
                 Line::new_builder()
                     .set_start(self.settings.res()?.staff_offset_mm, current_pos)
-                    .set_end(
-                        (notes.last().unwrap().abs - notes.first().unwrap().abs + overflow_notes)
-                            as f64
-                            * self.scale.res()?.x
-                            + self.settings.res()?.staff_offset_mm,
-                        current_pos,
-                    )
+                    .set_end(end_x, current_pos)
                     .set_stroke(self.settings.res()?.staff_line_colour.clone())
                     .set_stroke_width(self.settings.res()?.staff_line_thickness_mm)
                     .finish(),
             );
         }
 
+        // Draw the glue area and the alignment marks on both rows of the join.
+        // The start of the glue area repeats the strip a glue area's length before the end of the row before, so both marks are at the same tick
+        if glue > 0f64 {
+            let edge = self.edge_distance()?;
+            document.append(
+                Layer::Glue,
+                Rect::new_builder()
+                    .set_position(self.settings.res()?.staff_offset_mm, staff_top - edge)
+                    .set_size(glue, staff_bot - staff_top + 2f64 * edge)
+                    .set_fill(self.settings.res()?.glue_colour.clone())
+                    .finish(),
+            );
+            self.draw_alignment_mark(&mut document, self.settings.res()?.staff_offset_mm, staff_y)?;
+        }
+        if !last && self.settings.res()?.glue_overlap_mm > 0f64 {
+            self.draw_alignment_mark(
+                &mut document,
+                end_x - self.settings.res()?.glue_overlap_mm,
+                staff_y,
+            )?;
+        }
+
+        // Draw bar and beat lines
+        self.draw_grid(
+            &mut document,
+            start_abs,
+            notes.last().unwrap().abs,
+            origin_x,
+            staff_y,
+            first,
+        )?;
+
+        // Draw note labels in front of the staff lines
+        if self.settings.res()?.note_label_enable {
+            let font_size = self.settings.res()?.note_label_font_size_mm;
+            for i in 0..self.music_box.res()?.note_count() {
+                let name = self.music_box.res()?.get_note(i).res()?.to_string();
+                document.append(
+                    Layer::Labels,
+                    Text::new_builder()
+                        // Roughly centres the capitals on the line
+                        .set_position(
+                            self.settings.res()?.staff_offset_mm - NOTE_LABEL_GAP_MM,
+                            staff_y + self.music_box.res()?.note_y_mm(i) + font_size * 0.35f64,
+                        )
+                        .set_text(name)
+                        .set_font_size(font_size)
+                        .set_fill(self.settings.res()?.note_label_colour.clone())
+                        .set_anchor(TextAnchor::End)
+                        .finish(),
+                );
+            }
+        }
+
         // Draw staff bounding box
         // Left
         document.append(
+            Layer::Score,
             Line::new_builder()
-                .set_start(
-                    self.settings.res()?.staff_offset_mm,
-                    self.settings.res()?.staff_offset_mm
-                        - self
-                            .settings
-                            .res()?
-                            .staff_bounding_box_top_bottom_distance_mm,
-                )
-                .set_end(
-                    self.settings.res()?.staff_offset_mm,
-                    self.scale.res()?.y * (self.music_box.res()?.note_count() as f64 - 1f64)
-                        + self.settings.res()?.staff_offset_mm
-                        + self
-                            .settings
-                            .res()?
-                            .staff_bounding_box_top_bottom_distance_mm,
-                )
+                .set_start(self.settings.res()?.staff_offset_mm, top_y)
+                .set_end(self.settings.res()?.staff_offset_mm, bot_y)
                 .set_stroke(
                     self.settings
                         .res()?
@@ -323,30 +794,10 @@ impl MusicBoxConvert {
 
         // Right
         document.append(
+            Layer::Score,
             Line::new_builder()
-                .set_start(
-                    (notes.last().unwrap().abs - notes.first().unwrap().abs + overflow_notes)
-                        as f64
-                        * self.scale.res()?.x
-                        + self.settings.res()?.staff_offset_mm,
-                    self.settings.res()?.staff_offset_mm
-                        - self
-                            .settings
-                            .res()?
-                            .staff_bounding_box_top_bottom_distance_mm,
-                )
-                .set_end(
-                    (notes.last().unwrap().abs - notes.first().unwrap().abs + overflow_notes)
-                        as f64
-                        * self.scale.res()?.x
-                        + self.settings.res()?.staff_offset_mm,
-                    self.scale.res()?.y * (self.music_box.res()?.note_count() as f64 - 1f64)
-                        + self.settings.res()?.staff_offset_mm
-                        + self
-                            .settings
-                            .res()?
-                            .staff_bounding_box_top_bottom_distance_mm,
-                )
+                .set_start(end_x, top_y)
+                .set_end(end_x, bot_y)
                 .set_stroke(
                     self.settings
                         .res()?
@@ -359,26 +810,10 @@ impl MusicBoxConvert {
 
         // Top
         document.append(
+            Layer::Score,
             Line::new_builder()
-                .set_start(
-                    self.settings.res()?.staff_offset_mm,
-                    self.settings.res()?.staff_offset_mm
-                        - self
-                            .settings
-                            .res()?
-                            .staff_bounding_box_top_bottom_distance_mm,
-                )
-                .set_end(
-                    (notes.last().unwrap().abs - notes.first().unwrap().abs + overflow_notes)
-                        as f64
-                        * self.scale.res()?.x
-                        + self.settings.res()?.staff_offset_mm,
-                    self.settings.res()?.staff_offset_mm
-                        - self
-                            .settings
-                            .res()?
-                            .staff_bounding_box_top_bottom_distance_mm,
-                )
+                .set_start(self.settings.res()?.staff_offset_mm, top_y)
+                .set_end(end_x, top_y)
                 .set_stroke(
                     self.settings
                         .res()?
@@ -391,28 +826,10 @@ impl MusicBoxConvert {
 
         // Bottom
         document.append(
+            Layer::Score,
             Line::new_builder()
-                .set_start(
-                    self.settings.res()?.staff_offset_mm,
-                    self.scale.res()?.y * (self.music_box.res()?.note_count() as f64 - 1f64)
-                        + self.settings.res()?.staff_offset_mm
-                        + self
-                            .settings
-                            .res()?
-                            .staff_bounding_box_top_bottom_distance_mm,
-                )
-                .set_end(
-                    (notes.last().unwrap().abs - notes.first().unwrap().abs + overflow_notes)
-                        as f64
-                        * self.scale.res()?.x
-                        + self.settings.res()?.staff_offset_mm,
-                    self.scale.res()?.y * (self.music_box.res()?.note_count() as f64 - 1f64)
-                        + self.settings.res()?.staff_offset_mm
-                        + self
-                            .settings
-                            .res()?
-                            .staff_bounding_box_top_bottom_distance_mm,
-                )
+                .set_start(self.settings.res()?.staff_offset_mm, bot_y)
+                .set_end(end_x, bot_y)
                 .set_stroke(
                     self.settings
                         .res()?
@@ -423,60 +840,104 @@ impl MusicBoxConvert {
                 .finish(),
         );
 
-        // Draw notes
-        let first_note_pos = notes.first().unwrap().abs;
-        let mut stop_point_sprocket = u64::MIN;
+        // Draw cut line
+        if self.settings.res()?.cut_line_enable {
+            let cut_top_y = staff_top - self.settings.res()?.cut_line_distance_mm;
+            let cut_bot_y = staff_bot + self.settings.res()?.cut_line_distance_mm;
+            document.append(
+                Layer::Cut,
+                Path::new_builder()
+                    .move_to(self.settings.res()?.staff_offset_mm, cut_top_y)
+                    .line_to(end_x, cut_top_y)
+                    .line_to(end_x, cut_bot_y)
+                    .line_to(self.settings.res()?.staff_offset_mm, cut_bot_y)
+                    .close()
+                    .set_stroke(self.settings.res()?.cut_line_colour.clone())
+                    .set_stroke_width(self.settings.res()?.cut_line_thickness_mm)
+                    .finish(),
+            );
+        }
+
+        // Draw continuation marks. A chevron in front of a row that continues the strip and one after a row that is continued.
+        // Where the strip continues on another page, that page's number is printed below the chevron
+        if self.settings.res()?.continuation_mark_enable {
+            let size = self.settings.res()?.continuation_mark_size_mm;
+            let mid_y = (top_y + bot_y) / 2f64;
+            if !first {
+                // Keep clear of the note labels if there is enough room
+                let x = (self.settings.res()?.staff_offset_mm
+                    - 2f64 * size
+                    - self.note_label_width()?)
+                .max(0f64);
+                let previous_page = match row {
+                    0 => Some(page),
+                    _ => None,
+                };
+                self.draw_chevron(&mut document, x, mid_y, previous_page)?;
+            }
+            if !last {
+                let next_page = match row + 1 == self.rows_per_page()? {
+                    true => Some(page + 2),
+                    false => None,
+                };
+                self.draw_chevron(&mut document, end_x + size, mid_y, next_page)?;
+            }
+        }
+
+        // Draw notes. The notes of the row before that fall into the glue area are repeated
+        for event in glue_notes.iter().chain(notes) {
+            let x = (event.abs as f64 - start_abs as f64) * self.scale.res()?.x + origin_x;
+            if x < self.settings.res()?.staff_offset_mm {
+                continue;
+            }
 
-        for event in notes {
             info!("Drawing {}", event.note);
 
             let note_index = match self.music_box.res()?.get_index(&event.note) {
-                Some(t) => t + 1, // Zero based index
+                Some(t) => t,
                 None => continue,
             };
 
             document.append(
+                Layer::Holes,
                 Circle::new_builder()
-                    .set_centre(
-                        (event.abs + overflow_notes - first_note_pos) as f64 * self.scale.res()?.x
-                            + self.settings.res()?.staff_offset_mm,
-                        (self.music_box.res()?.note_count() - note_index) as f64
-                            * self.scale.res()?.y
-                            + self.settings.res()?.staff_offset_mm,
-                    )
+                    .set_centre(x, self.music_box.res()?.note_y_mm(note_index) + staff_y)
                     .set_radius(self.settings.res()?.note_hole_radius_mm)
                     .set_fill(self.settings.res()?.note_hole_colour.clone())
                     .finish(),
             );
-
-            stop_point_sprocket = event.abs - first_note_pos + overflow_notes;
         }
 
-        let mut current_x = 0f64;
+        // The sprocket holes continue the spacing of the row before. The first one on this row is overflow_sprockets after origin_x
+        let sprocket_distance = self.settings.res()?.sprocket_hole_distance_mm;
+        let first_sprocket_x = origin_x + overflow_sprockets;
+        let sprocket_count = match first_sprocket_x <= end_x {
+            true => ((end_x - first_sprocket_x) / sprocket_distance).floor() as u64 + 1,
+            false => 0,
+        };
 
         info!("Drawing sprocket holes");
         // Draw sprocket holes
         if self.settings.res()?.sprocket_hole_enable {
             // Y position of the top holes
-            let top_y = self.settings.res()?.staff_offset_mm
-                - self.settings.res()?.sprocket_hole_distance_staff_mm;
+            let top_y = staff_top - self.settings.res()?.sprocket_hole_distance_staff_mm;
 
             // Y position of the bottom holes
-            let bot_y = self.settings.res()?.staff_offset_mm
-                + ((self.music_box.res()?.note_count() - 1) as f64 * self.scale.res()?.y)
-                + self.settings.res()?.sprocket_hole_distance_staff_mm;
-
-            let page_size_x = self.settings.res()?.paper_size_x;
-            let sprocket_area =
-                self.scale.res()?.x * stop_point_sprocket as f64 - overflow_sprockets;
-            for i in
-                0..=(sprocket_area / self.settings.res()?.sprocket_hole_distance_mm).floor() as u64
-            {
-                current_x = (self.settings.res()?.staff_offset_mm + overflow_sprockets)
-                    + (i as f64 * self.settings.res()?.sprocket_hole_distance_mm);
+            let bot_y = staff_bot + self.settings.res()?.sprocket_hole_distance_staff_mm;
+
+            // The sprocket holes of the row before that fall into the glue area are repeated
+            let glue_count = match glue > 0f64 {
+                true => ((first_sprocket_x - self.settings.res()?.staff_offset_mm)
+                    / sprocket_distance)
+                    .floor() as i64,
+                false => 0,
+            };
+            for i in -glue_count..sprocket_count as i64 {
+                let current_x = first_sprocket_x + i as f64 * sprocket_distance;
 
                 // Top hole
                 document.append(
+                    Layer::Sprockets,
                     Circle::new_builder()
                         .set_centre(current_x, top_y)
                         .set_radius(self.settings.res()?.note_hole_radius_mm)
@@ -486,6 +947,7 @@ impl MusicBoxConvert {
 
                 // Bottom hole
                 document.append(
+                    Layer::Sprockets,
                     Circle::new_builder()
                         .set_centre(current_x, bot_y)
                         .set_radius(self.settings.res()?.note_hole_radius_mm)
@@ -497,8 +959,279 @@ impl MusicBoxConvert {
 
         self.svg.push(document);
 
-        // Return the overflow of the sprocket holes
-        Ok(self.settings.res()?.paper_size_x - self.settings.res()?.staff_offset_mm - current_x)
+        // Return the distance from the end of the row to where the next sprocket hole would be
+        Ok(first_sprocket_x + sprocket_count as f64 * sprocket_distance - end_x)
+    }
+
+    /// Returns the events of the page before the last one, or nothing if there is only one page
+    fn previous_page(pages: &[Vec<Event>]) -> &[Event] {
+        match pages.len() {
+            0 | 1 => &[],
+            t => &pages[t - 2],
+        }
+    }
+
+    /// Returns the width of the glue area at the start of a row. The first row has none
+    fn glue_width(&self, first: bool) -> Result<f64> {
+        Ok(match first {
+            true => 0f64,
+            false => self.settings.res()?.glue_overlap_mm.max(0f64),
+        })
+    }
+
+    /// Draws short vertical lines above and below a strip row. The mark at the start of a glue area lines up with the mark a glue area's length before the end of the row before when they are glued together
+    fn draw_alignment_mark(&self, document: &mut Document, x: f64, staff_y: f64) -> Result<()> {
+        let settings = self.settings.res()?;
+        let edge = self.edge_distance()?;
+        let size = settings.continuation_mark_size_mm;
+        let (staff_top, staff_bot) = self.music_box.res()?.note_y_range_mm();
+        let top_y = staff_y + staff_top - edge;
+        let bot_y = staff_y + staff_bot + edge;
+        for (start_y, end_y) in [(top_y - size, top_y), (bot_y, bot_y + size)] {
+            document.append(
+                Layer::Decoration,
+                Line::new_builder()
+                    .set_start(x, start_y)
+                    .set_end(x, end_y)
+                    .set_stroke(settings.continuation_mark_colour.clone())
+                    .set_stroke_width(settings.staff_bounding_box_thickness_mm)
+                    .finish(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Draws a chevron pointing right. The chevron starts at the given x position and is centred on the given y position.
+    /// If a one-based page number is passed, it's printed below the chevron
+    fn draw_chevron(
+        &self,
+        document: &mut Document,
+        x: f64,
+        y: f64,
+        page: Option<usize>,
+    ) -> Result<()> {
+        let settings = self.settings.res()?;
+        let size = settings.continuation_mark_size_mm;
+        for end_y in [y - size, y + size] {
+            document.append(
+                Layer::Decoration,
+                Line::new_builder()
+                    .set_start(x, end_y)
+                    .set_end(x + size, y)
+                    .set_stroke(settings.continuation_mark_colour.clone())
+                    .set_stroke_width(settings.staff_bounding_box_thickness_mm)
+                    .finish(),
+            );
+        }
+        if let Some(t) = page {
+            document.append(
+                Layer::Labels,
+                Text::new_builder()
+                    .set_position(
+                        x + size / 2f64,
+                        y + size + NOTE_LABEL_GAP_MM + settings.continuation_mark_font_size_mm,
+                    )
+                    .set_text(format!("p. {t}"))
+                    .set_font_size(settings.continuation_mark_font_size_mm)
+                    .set_fill(settings.continuation_mark_colour.clone())
+                    .set_anchor(TextAnchor::Middle)
+                    .finish(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Don't call manually.
+    /// It's called by <code>self.generate_svgs</code> once all pages are drawn.
+    /// Prints the title, the name of the music box and the page number along the top edge of every page.
+    fn draw_headers(&mut self) -> Result<()> {
+        let settings = self.settings.res()?;
+        if !settings.page_header_enable {
+            return Ok(());
+        }
+
+        let font_size = settings.page_header_font_size_mm;
+        let left_x = settings.staff_offset_mm;
+        let right_x = settings.paper_size_x - settings.staff_offset_mm;
+        let y = self.page_margin()? + font_size;
+        let title = self.title.clone().unwrap_or_default();
+        let box_name = self.music_box.res()?.name.clone();
+        let colour = settings.page_header_colour.clone();
+
+        let count = self.svg.len();
+        for (i, document) in self.svg.iter_mut().enumerate() {
+            for (x, text, anchor) in [
+                (left_x, title.clone(), TextAnchor::Start),
+                (
+                    (left_x + right_x) / 2f64,
+                    box_name.clone(),
+                    TextAnchor::Middle,
+                ),
+                (
+                    right_x,
+                    format!("page {} of {count}", i + 1),
+                    TextAnchor::End,
+                ),
+            ] {
+                if text.is_empty() {
+                    continue;
+                }
+                document.append(
+                    Layer::Labels,
+                    Text::new_builder()
+                        .set_position(x, y)
+                        .set_text(text)
+                        .set_font_size(font_size)
+                        .set_fill(colour.clone())
+                        .set_anchor(anchor)
+                        .finish(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the margin between the edge of the page and the outermost line of the first strip row.
+    /// The same margin is kept below the last row and between the page header and the first row
+    fn page_margin(&self) -> Result<f64> {
+        Ok((self.settings.res()?.staff_offset_mm - self.edge_distance()?).max(0f64))
+    }
+
+    /// Draws the bar lines, beat lines and bar numbers of one strip row. The row spans the passed tempo-adjusted ticks, start_abs is at origin_x.
+    /// Beats in the glue area are repeated without their bar numbers. Without a glue area, beats on the start of a row were already drawn at the end of the row before.
+    fn draw_grid(
+        &self,
+        document: &mut Document,
+        start_abs: u64,
+        end_abs: u64,
+        origin_x: f64,
+        staff_y: f64,
+        first: bool,
+    ) -> Result<()> {
+        let settings = self.settings.res()?;
+        if !(settings.bar_line_enable || settings.beat_line_enable || settings.bar_number_enable) {
+            return Ok(());
+        }
+        let time_signatures = match &self.time_signatures {
+            Some(t) => t,
+            None => return Ok(()),
+        };
+        let tempo_map = self.tempo_map.res()?;
+        let (staff_top, staff_bot) = self.music_box.res()?.note_y_range_mm();
+        let (top_y, bot_y) = (staff_y + staff_top, staff_y + staff_bot);
+
+        for beat in time_signatures.beats(tempo_map.unadjust(end_abs)) {
+            let abs = tempo_map.adjust(beat.tick);
+            let x = (abs as f64 - start_abs as f64) * self.scale.res()?.x + origin_x;
+            let repeated = abs < start_abs || (abs == start_abs && !first);
+            if abs > end_abs
+                || x < settings.staff_offset_mm
+                || (repeated && origin_x <= settings.staff_offset_mm)
+            {
+                continue;
+            }
+
+            let (enable, colour, thickness) = match beat.downbeat {
+                true => (
+                    settings.bar_line_enable,
+                    &settings.bar_line_colour,
+                    settings.bar_line_thickness_mm,
+                ),
+                false => (
+                    settings.beat_line_enable,
+                    &settings.beat_line_colour,
+                    settings.beat_line_thickness_mm,
+                ),
+            };
+            if enable {
+                document.append(
+                    Layer::Grid,
+                    Line::new_builder()
+                        .set_start(x, top_y)
+                        .set_end(x, bot_y)
+                        .set_stroke(colour.clone())
+                        .set_stroke_width(thickness)
+                        .finish(),
+                );
+            }
+
+            if beat.downbeat && settings.bar_number_enable && !repeated {
+                document.append(
+                    Layer::Labels,
+                    Text::new_builder()
+                        .set_position(x, top_y - self.edge_distance()? - NOTE_LABEL_GAP_MM)
+                        .set_text(beat.bar.to_string())
+                        .set_font_size(settings.bar_number_font_size_mm)
+                        .set_fill(settings.bar_line_colour.clone())
+                        .set_anchor(TextAnchor::Middle)
+                        .finish(),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns roughly how far the note labels reach to the left of the staff. Zero if they're disabled
+    fn note_label_width(&self) -> Result<f64> {
+        let settings = self.settings.res()?;
+        if !settings.note_label_enable {
+            return Ok(0f64);
+        }
+        let music_box = self.music_box.res()?;
+        let chars = (0..music_box.note_count())
+            .filter_map(|i| music_box.get_note(i))
+            .map(|note| note.to_string().chars().count())
+            .max()
+            .unwrap_or(0);
+        // Average glyph width of a sans-serif font is a bit over half the font size
+        Ok(NOTE_LABEL_GAP_MM + chars as f64 * 0.6f64 * settings.note_label_font_size_mm)
+    }
+
+    /// Returns the room the page header takes above the first strip row. Zero if it's disabled
+    fn header_height(&self) -> Result<f64> {
+        let settings = self.settings.res()?;
+        match settings.page_header_enable {
+            true => Ok(settings.page_header_font_size_mm + self.page_margin()?),
+            false => Ok(0f64),
+        }
+    }
+
+    /// Returns the vertical position of the top staff line of a strip row
+    fn staff_y(&self, row: usize) -> Result<f64> {
+        Ok(self.settings.res()?.staff_offset_mm
+            + self.header_height()?
+            + row as f64 * self.row_pitch()?)
+    }
+
+    /// Returns the vertical distance between the top staff lines of two strip rows
+    fn row_pitch(&self) -> Result<f64> {
+        Ok(self.music_box.res()?.strip_height_mm
+            + 2f64 * self.edge_distance()?
+            + self.settings.res()?.row_gap_mm)
+    }
+
+    /// Returns the distance of the outermost line of a row, the bounding box or the cut line, to the staff
+    fn edge_distance(&self) -> Result<f64> {
+        let settings = self.settings.res()?;
+        match settings.cut_line_enable {
+            true => Ok(settings
+                .staff_bounding_box_top_bottom_distance_mm
+                .max(settings.cut_line_distance_mm)),
+            false => Ok(settings.staff_bounding_box_top_bottom_distance_mm),
+        }
+    }
+
+    /// Returns how many strip rows fit on one page. Always one if row stacking is disabled
+    fn rows_per_page(&self) -> Result<usize> {
+        let settings = self.settings.res()?;
+        if !settings.row_stacking_enable {
+            return Ok(1);
+        }
+
+        // The margin above the first row is kept below the last row as well. The page header needs room for one more line
+        let usable = settings.paper_size_y - 2f64 * self.page_margin()? - self.header_height()?
+            + settings.row_gap_mm;
+        Ok(((usable / self.row_pitch()?).floor() as usize).max(1))
     }
 
     /// Writes the documents to a file
@@ -514,15 +1247,63 @@ impl MusicBoxConvert {
             Err(e) => return Err(Error::IOError(Box::new(e), Box::new(path_string))),
         }
 
-        for (i, svg) in self.svg.iter().enumerate() {
-            let mut path_i = abs_path.clone();
-            path_i.push(i.to_string() + ".svg");
-            svg.save(std::path::Path::new(&path_i))?
+        match self.args.get_one::<String>("format").unwrap().as_str() {
+            "pdf" => {
+                let mut pdf = PdfWriter::new();
+                for svg in self.svg.iter() {
+                    pdf.add_page(svg)?;
+                }
+                abs_path.push("score.pdf");
+                pdf.save(std::path::Path::new(&abs_path))?
+            }
+            "dxf" => {
+                let engrave = self.args.get_flag("engrave");
+                for (i, svg) in self.svg.iter().enumerate() {
+                    let mut path_i = abs_path.clone();
+                    path_i.push(i.to_string() + ".dxf");
+                    DxfWriter::from_document(svg, engrave)?.save(std::path::Path::new(&path_i))?
+                }
+            }
+            "gcode" => {
+                for (i, svg) in self.svg.iter().enumerate() {
+                    let mut path_i = abs_path.clone();
+                    path_i.push(i.to_string() + ".gcode");
+                    GcodeWriter::from_document(svg, self.settings.res()?)?
+                        .save(std::path::Path::new(&path_i))?
+                }
+            }
+            "png" => {
+                let dpi = *self.args.get_one::<f64>("dpi").unwrap();
+                for (i, svg) in self.svg.iter().enumerate() {
+                    let mut path_i = abs_path.clone();
+                    path_i.push(i.to_string() + ".png");
+                    PngWriter::from_document(svg, dpi)?.save(std::path::Path::new(&path_i))?
+                }
+            }
+            _ => {
+                for (i, svg) in self.svg.iter().enumerate() {
+                    let mut path_i = abs_path.clone();
+                    path_i.push(i.to_string() + ".svg");
+                    svg.save(std::path::Path::new(&path_i))?
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Returns a Vec of png files rendered from the documents
+    fn output_pngs(&self) -> Result<Vec<Vec<u8>>> {
+        let dpi = *self.args.get_one::<f64>("dpi").unwrap();
+        let mut pngs = Vec::<Vec<u8>>::new();
+
+        for svg in self.svg.iter() {
+            pngs.push(PngWriter::from_document(svg, dpi)?.print()?);
+        }
+
+        Ok(pngs)
+    }
+
     /// Returns a Vec of strings containing the documents
     fn output_documents(&self) -> Result<Vec<String>> {
         let mut docs = Vec::<String>::new();
@@ -534,3 +1315,273 @@ impl MusicBoxConvert {
         Ok(docs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{MusicBoxConvert, NOTE_LABEL_GAP_MM};
+
+    /// The 30-note music box with a crank advance of 20mm per turn
+    const CRANK_BOX: &str = "tests/crank_box.json";
+
+    /// Loads the test midi file with the given extra arguments and scales it like the convert subcommand does
+    fn converter(args: &[&str]) -> MusicBoxConvert {
+        try_converter(args).unwrap()
+    }
+
+    fn try_converter(args: &[&str]) -> crate::prelude::Result<MusicBoxConvert> {
+        let mut all = vec![
+            "program_name",
+            "convert",
+            "-i",
+            "meg_wiwauf_laminat1.mid",
+            "-o",
+            "./out/",
+            "-qt",
+        ];
+        all.extend_from_slice(args);
+        let matches = crate::command::get_command().get_matches_from(all);
+
+        let mut converter = MusicBoxConvert::new(matches.subcommand_matches("convert").unwrap());
+        converter.load_music_box()?;
+        converter.load_settings()?;
+        converter.get_abs()?;
+        converter.set_scale_factor()?;
+        Ok(converter)
+    }
+
+    #[test]
+    fn crank_length() {
+        // The built-in music boxes weren't measured
+        assert!(try_converter(&["--crank-rate", "30"]).is_err());
+
+        // 30 turns per minute of 20mm per turn move the strip 10mm per second
+        let converter = converter(&["--crank-rate", "30", "-b", CRANK_BOX]);
+        let track = converter.track.as_ref().unwrap();
+        let first = track.iter().next().unwrap().abs;
+        let last = track.iter().last().unwrap().abs;
+
+        let length = (last - first) as f64 * converter.scale.as_ref().unwrap().x;
+        let seconds = converter.tempo_map.as_ref().unwrap().seconds(last - first);
+        assert!((length - seconds * 10f64).abs() < 1e-6);
+    }
+
+    #[test]
+    fn crank_conflicts() {
+        let fitted = converter(&[]);
+        let converter = converter(&["--crank-rate", "10", "-b", CRANK_BOX]);
+
+        // The strip isn't rescaled to fit the closest notes, they are reported instead
+        assert!(converter.scale.as_ref().unwrap().x < fitted.scale.as_ref().unwrap().x);
+        assert!(fitted.conflict_report().unwrap().conflicts.is_empty());
+        let report = converter.conflict_report().unwrap();
+        assert!(!report.conflicts.is_empty());
+        assert!(report
+            .conflicts
+            .iter()
+            .all(|el| el.distance_mm < report.min_note_distance_mm));
+    }
+
+    #[test]
+    fn row_stacking() {
+        let mut single = converter(&[]);
+        single.generate_svgs().unwrap();
+
+        let mut stacked = converter(&[]);
+        stacked.settings.as_mut().unwrap().row_stacking_enable = true;
+        stacked.generate_svgs().unwrap();
+
+        let rows_per_page = stacked.rows_per_page().unwrap();
+        assert!(rows_per_page > 1);
+        assert_eq!(stacked.svg.len(), single.svg.len().div_ceil(rows_per_page));
+
+        // The top staff line of the second row is one row pitch below the first one
+        let staff_offset = stacked.settings.as_ref().unwrap().staff_offset_mm;
+        let second_row = stacked.staff_y(1).unwrap();
+        assert_eq!(
+            second_row,
+            stacked.staff_y(0).unwrap() + stacked.row_pitch().unwrap()
+        );
+        let page = stacked.svg[0].print();
+        assert!(page.contains(&format!(r#"x1="{staff_offset}" y1="{second_row}""#)));
+    }
+
+    #[test]
+    fn cut_line() {
+        let mut cut = converter(&[]);
+        cut.generate_svgs().unwrap();
+        let settings = cut.settings.as_ref().unwrap();
+        let start = settings.staff_offset_mm;
+        let top = cut.staff_y(0).unwrap() - settings.cut_line_distance_mm;
+        let bottom = cut.staff_y(0).unwrap()
+            + cut.music_box.as_ref().unwrap().strip_height_mm
+            + settings.cut_line_distance_mm;
+        for document in cut.svg.iter() {
+            let page = document.print();
+            assert!(page.contains(&format!(r#"<path d="M {start} {top} L "#)));
+            assert!(page.contains(&format!(" L {start} {bottom} Z")));
+        }
+
+        let mut uncut = converter(&[]);
+        uncut.settings.as_mut().unwrap().cut_line_enable = false;
+        uncut.generate_svgs().unwrap();
+        assert!(!uncut.svg[0].print().contains(r#"<g id="cut">"#));
+    }
+
+    #[test]
+    fn note_labels() {
+        let mut converter = converter(&[]);
+        converter.settings.as_mut().unwrap().note_label_enable = true;
+        converter.generate_svgs().unwrap();
+
+        // Every note line is labelled, right aligned just in front of the staff
+        let settings = converter.settings.as_ref().unwrap();
+        let music_box = converter.music_box.as_ref().unwrap();
+        let page = converter.svg[0].print();
+        for i in 0..music_box.note_count() {
+            let y = converter.staff_y(0).unwrap()
+                + music_box.note_y_mm(i)
+                + settings.note_label_font_size_mm * 0.35f64;
+            assert!(page.contains(&format!(
+                r#"<text x="{0}" y="{y}" font-size="{1}" font-family="sans-serif" fill="{2}" text-anchor="end">{3}</text>"#,
+                settings.staff_offset_mm - NOTE_LABEL_GAP_MM,
+                settings.note_label_font_size_mm,
+                settings.note_label_colour,
+                music_box.get_note(i).unwrap()
+            )));
+        }
+    }
+
+    #[test]
+    fn page_headers() {
+        let mut converter = converter(&["--title", "Laminat"]);
+        converter.generate_svgs().unwrap();
+        assert!(converter.svg.len() > 1);
+
+        // Along the top edge, above the first strip row
+        let y = converter.page_margin().unwrap()
+            + converter
+                .settings
+                .as_ref()
+                .unwrap()
+                .page_header_font_size_mm;
+        assert!(y < converter.staff_y(0).unwrap() - converter.edge_distance().unwrap());
+        let count = converter.svg.len();
+        for (i, document) in converter.svg.iter().enumerate() {
+            let header = format!(
+                r##"<text x="10" y="{y}" font-size="3" font-family="sans-serif" fill="#000000" text-anchor="start">Laminat</text>
+<text x="148.5" y="{y}" font-size="3" font-family="sans-serif" fill="#000000" text-anchor="middle">30-note</text>
+<text x="287" y="{y}" font-size="3" font-family="sans-serif" fill="#000000" text-anchor="end">page {0} of {count}</text>"##,
+                i + 1
+            );
+            assert!(document.print().contains(&header));
+        }
+    }
+
+    /// Returns the x positions of the circles with the given colour
+    fn circles(page: &str, fill: &str) -> Vec<f64> {
+        page.lines()
+            .filter(|el| el.starts_with("<circle") && el.contains(&format!(r#"fill="{fill}""#)))
+            .map(|el| el.split('"').nth(1).unwrap().parse::<f64>().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn glue_area() {
+        let mut converter = converter(&[]);
+        converter.settings.as_mut().unwrap().glue_overlap_mm = 40f64;
+        converter.generate_svgs().unwrap();
+        let settings = converter.settings.as_ref().unwrap();
+        let origin_x = settings.staff_offset_mm + settings.glue_overlap_mm;
+        let (first, second) = (converter.svg[0].print(), converter.svg[1].print());
+
+        // Only rows that continue the strip start with a glue area
+        assert!(!first.contains(r#"<g id="glue">"#));
+        assert!(second.contains(&format!(
+            r#"<rect x="{0}" y="{1}" width="{2}" "#,
+            settings.staff_offset_mm,
+            converter.staff_y(0).unwrap() - converter.edge_distance().unwrap(),
+            settings.glue_overlap_mm
+        )));
+
+        // The end of the first row is repeated in the glue area of the second one
+        let end_x = first
+            .lines()
+            .find(|el| el.starts_with("<line"))
+            .unwrap()
+            .split('"')
+            .nth(5)
+            .unwrap()
+            .parse::<f64>()
+            .unwrap();
+        let holes = circles(&first, &settings.note_hole_colour);
+        let repeated = circles(&second, &settings.note_hole_colour)
+            .into_iter()
+            .filter(|el| *el < origin_x)
+            .collect::<Vec<f64>>();
+        assert!(!repeated.is_empty());
+        for x in repeated {
+            assert!(holes
+                .iter()
+                .any(|el| (el - end_x - (x - origin_x)).abs() < 1e-6));
+        }
+
+        // The alignment marks of both rows are at the same tick, the start of the glue area
+        let marks = |page: &str| {
+            page.lines()
+                .skip_while(|el| *el != r#"<g id="decorations">"#)
+                .take_while(|el| *el != "</g>")
+                .map(|el| el.split('"').collect::<Vec<&str>>())
+                .filter(|el| el.len() > 7 && el[1] == el[5])
+                .map(|el| el[1].parse::<f64>().unwrap())
+                .collect::<Vec<f64>>()
+        };
+        let end_mark = marks(&first)[0];
+        let start_mark = marks(&second).into_iter().fold(f64::MAX, f64::min);
+        assert!((end_mark - (end_x - settings.glue_overlap_mm)).abs() < 1e-6);
+        assert!((start_mark - settings.staff_offset_mm).abs() < 1e-6);
+        assert!(((end_x - end_mark) - (origin_x - start_mark)).abs() < 1e-6);
+
+        // The sprocket holes keep their spacing across the join
+        let sprocket = &settings.sprocket_hole_colour;
+        let last = circles(&first, sprocket)
+            .into_iter()
+            .fold(f64::MIN, f64::max);
+        let next = circles(&second, sprocket)
+            .into_iter()
+            .filter(|el| *el >= origin_x)
+            .fold(f64::MAX, f64::min);
+        assert!(
+            ((end_x - last) + (next - origin_x) - settings.sprocket_hole_distance_mm).abs() < 1e-6
+        );
+    }
+
+    #[test]
+    fn beat_length() {
+        let converter = converter(&["--mm-per-beat", "4"]);
+        let ticks_per_beat = converter
+            .tempo_map
+            .as_ref()
+            .unwrap()
+            .ticks_per_beat()
+            .unwrap();
+        let scale_x = converter.scale.as_ref().unwrap().x;
+        assert!((scale_x * ticks_per_beat as f64 - 4f64).abs() < 1e-9);
+
+        // The report uses the given scale instead of the fitted one
+        let report = converter.conflict_report().unwrap();
+        assert_eq!(report.mm_per_tick, scale_x);
+        assert!(!report.conflicts.is_empty());
+    }
+
+    #[test]
+    fn beat_length_resolve() {
+        let converter = converter(&["--mm-per-beat", "4", "--resolve", "nudge,drop"]);
+        let min_note_distance_mm = converter.music_box.as_ref().unwrap().min_note_distance_mm;
+        assert!(converter.conflict_report().unwrap().conflicts.is_empty());
+
+        // The minimum distance follows the resolved notes
+        let min_distance_mm = converter.track.as_ref().unwrap().min_distance() as f64
+            * converter.scale.as_ref().unwrap().x;
+        assert!(min_distance_mm > min_note_distance_mm - 1e-9);
+    }
+}
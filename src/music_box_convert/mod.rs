@@ -10,7 +10,9 @@ use midly::Smf;
 
 // Internal
 use crate::{
-    music::{music_box::MusicBox, track::Track},
+    music::{
+        music_box::MusicBox, tempo_map::TempoMap, time_signature::TimeSignatureMap, track::Track,
+    },
     settings::{self, Settings},
     svg_writer::document::Document,
     vec2::Vec2,
@@ -28,6 +30,14 @@ pub struct MusicBoxConvert {
     svg: Vec<Document>,
     /// The Track we are currently working on
     track: Option<Track>,
+    /// The tempo map of the midi file
+    tempo_map: Option<TempoMap>,
+    /// The bars and beats of the midi file. `None` for SMPTE timecode
+    time_signatures: Option<TimeSignatureMap>,
+    /// The title printed in the page header
+    title: Option<String>,
+    /// The tempo-adjusted tick of the first note of every page
+    page_starts: Vec<u64>,
     /// The scale factor
     scale: Option<Vec2<f64>>,
 }
@@ -0,0 +1,112 @@
+// clap
+use clap::{value_parser, Arg, ArgAction, Command};
+
+// Internal
+use super::MusicBoxDecode;
+
+impl MusicBoxDecode {
+    /// Returns the `Command` for the 'MusicBoxDecode' program
+    pub fn get_command() -> Command {
+        Command::new("decode")
+        .about("Reads strips (*.svg) written by the converter or scanned strips (*.png|*.jpg) back into a MIDI file (*.mid)")
+        .version("1.0.1")
+        .arg_required_else_help(true)
+        .author("Johanna Wehner, superjohannaa@gmail.com")
+        .arg(
+            Arg::new("io_in")
+                .short('i')
+                .long("input")
+                .help("The svg files written by the converter or the scanned strips to read. Multiple pages are seperated by spaces and stitched together in the given order. Files named like the converter output (0.svg, 1.svg, ...) are sorted by their page number.")
+                .num_args(1..)
+                .value_name("FILE")
+                .required(true),
+        )
+        .arg(
+            Arg::new("io_out")
+                .short('o')
+                .long("output")
+                .help("The midi file to output to.")
+                .num_args(1)
+                .value_name("FILE")
+                .required(true),
+        )
+        .arg(
+            Arg::new("io_box")
+                .short('b')
+                .long("box")
+                .help("Specifies which box file to use. The built-in music boxes are used if the default file doesn't exist.")
+                .default_value("./box.json")
+                .num_args(1)
+                .value_name("FILE")
+                .required(false),
+        )
+        .arg(
+            Arg::new("box_name")
+                .short('n')
+                .long("box-name")
+                .help("Specifies which music box from the box file the strips were made for. Uses the first one if not set.")
+                .num_args(1)
+                .value_name("NAME")
+                .required(false),
+        )
+        .arg(
+            Arg::new("mm_per_beat")
+                .long("mm-per-beat")
+                .help("The length of one beat on the strip in millimetres.")
+                .value_parser(value_parser!(f64))
+                .num_args(1)
+                .value_name("MM")
+                .required(true),
+        )
+        .arg(
+            Arg::new("ticks_per_beat")
+                .long("ticks-per-beat")
+                .help("The resolution of the midi file.")
+                .default_value("480")
+                .value_parser(value_parser!(u16).range(1..=0x7fff))
+                .num_args(1)
+                .value_name("TICKS")
+                .required(false),
+        )
+        .arg(
+            Arg::new("tempo")
+                .long("tempo")
+                .help("The tempo written into the midi file. In beats per minute.")
+                .default_value("120")
+                .value_parser(value_parser!(f64))
+                .num_args(1)
+                .value_name("BPM")
+                .required(false),
+        )
+        .arg(
+            Arg::new("dpi")
+                .long("dpi")
+                .help("The resolution of scanned or photographed strips in dots per inch. Needed for png and jpeg files.")
+                .value_parser(value_parser!(f64))
+                .num_args(1)
+                .value_name("DPI")
+                .required(false),
+        )
+        .arg(
+            Arg::new("verbosity")
+                .short('v')
+                .long("verbose")
+                .help("Increases verbosity. Can be used multiple times to raise log level.")
+                .default_value("0")
+                .num_args(0)
+                .action(ArgAction::Count)
+                .required(false)
+                .conflicts_with("quiet"),
+        )
+        .arg(
+            Arg::new("quiet")
+                .short('q')
+                .long("quiet")
+                .help("No Output. Exclusive to verbosity")
+                .default_value("false")
+                .num_args(0)
+                .conflicts_with("verbosity"),
+        )
+        .help_template(crate::command::HELP_TEMPLATE)
+    }
+}
@@ -0,0 +1,501 @@
+// std
+use std::{
+    fs::{self, File},
+    io::Write,
+};
+
+// simplelog
+use simplelog::*;
+
+// midly
+use midly::{
+    num::{u15, u24, u28},
+    Format, Header, MetaMessage, Smf, Timing, Track as MidiTrack, TrackEvent, TrackEventKind,
+};
+
+// Internal
+use super::{MusicBoxDecode, Row};
+use crate::{
+    image_reader::ImageReader,
+    music::{event::Event, music_box::MusicBox, track::Track},
+    prelude::*,
+    svg_reader::{Element, SvgReader},
+};
+
+/// How far apart two positions in millimetres can be and still be the same. The converter writes positions with full precision
+const TOLERANCE_MM: f64 = 0.01f64;
+
+/// The velocity of the decoded notes. A strip doesn't know how loud a note was
+const VELOCITY: u8 = 64;
+
+/// The smallest hole looked for in images. Printed lines have to be thinner than half of it
+const HOLE_MIN_DIAMETER_MM: f64 = 1f64;
+
+/// The largest hole looked for in images
+const HOLE_MAX_DIAMETER_MM: f64 = 5f64;
+
+/// The step size of the search for the note lines in images. The best step is searched again ten times finer
+const FIT_STEP_MM: f64 = 0.05f64;
+
+/// How much moving the note lines away from the centre of the strip costs in the search. Only decides between equally good positions
+const FIT_CENTRE_WEIGHT: f64 = 0.01f64;
+
+const MM_PER_INCH: f64 = 25.4f64;
+
+impl MusicBoxDecode {
+    pub fn run(mut self) -> Result<()> {
+        self.initiate_logger()?;
+        self.load_music_box()?;
+        let documents = self.read_documents()?;
+        self.decode(&documents)?;
+        self.write_midi()
+    }
+
+    pub fn run_output_track(mut self, documents: &[Vec<u8>]) -> Result<Track> {
+        self.initiate_logger()?;
+        self.load_music_box()?;
+        self.decode(documents)?;
+        Ok(self.track.take().unwrap())
+    }
+
+    /// Initiates the logger with the correct log level. The logger is static and so this musn't be called more than once
+    fn initiate_logger(&mut self) -> Result<()> {
+        let verbosity = self.args.get_count("verbosity");
+        let quiet = self.args.get_flag("quiet");
+        if quiet {
+            TermLogger::init(
+                LevelFilter::Off,
+                Config::default(),
+                TerminalMode::Mixed,
+                ColorChoice::Auto,
+            );
+            Ok(())
+        } else {
+            TermLogger::init(
+                match verbosity {
+                    0 => LevelFilter::Warn,
+                    1 => LevelFilter::Info,
+                    2 => LevelFilter::Debug,
+                    _ => LevelFilter::Trace,
+                },
+                Config::default(),
+                TerminalMode::Mixed,
+                ColorChoice::Always,
+            );
+            debug!(
+                "Verbosity set to {}",
+                match verbosity {
+                    2 => "Debug",
+                    _ => "Trace",
+                }
+            );
+            Ok(())
+        }
+    }
+
+    /// Loads the `MusicBox` the strips were made for
+    fn load_music_box(&mut self) -> Result<()> {
+        self.music_box = Some(MusicBox::load(
+            self.args.get_one::<String>("io_box").unwrap(),
+            self.args.get_one::<String>("box_name"),
+            self.args.value_source("io_box") != Some(clap::parser::ValueSource::DefaultValue),
+        )?);
+        Ok(())
+    }
+
+    /// Reads the svg files and images in page order
+    fn read_documents(&self) -> Result<Vec<Vec<u8>>> {
+        let mut paths = self
+            .args
+            .get_many::<String>("io_in")
+            .unwrap()
+            .cloned()
+            .collect::<Vec<String>>();
+
+        // Shells sort 10.svg before 2.svg, so files named like the converter output are sorted by their page number
+        let pages = paths
+            .iter()
+            .map(|el| {
+                std::path::Path::new(el)
+                    .file_stem()
+                    .and_then(|t| t.to_str())
+                    .and_then(|t| t.parse::<usize>().ok())
+            })
+            .collect::<Option<Vec<usize>>>();
+        if let Some(t) = pages {
+            let mut numbered = t.into_iter().zip(paths).collect::<Vec<(usize, String)>>();
+            numbered.sort_by_key(|el| el.0);
+            paths = numbered.into_iter().map(|el| el.1).collect();
+        }
+
+        paths
+            .iter()
+            .map(|el| match fs::read(el) {
+                Ok(t) => Ok(t),
+                Err(e) => Err(Error::IOError(Box::new(e), Box::new(el.clone()))),
+            })
+            .collect()
+    }
+
+    /// Decodes the note holes of all documents into self.track. The rows of all pages are stitched together in order
+    fn decode(&mut self, documents: &[Vec<u8>]) -> Result<()> {
+        let music_box = self.music_box.res()?;
+        let mm_per_beat = *self.args.get_one::<f64>("mm_per_beat").unwrap();
+        if mm_per_beat <= 0f64 {
+            return Err(Error::Generic(
+                "The millimetres per beat have to be above zero.".to_string(),
+            ));
+        }
+        let ticks_per_mm =
+            *self.args.get_one::<u16>("ticks_per_beat").unwrap() as f64 / mm_per_beat;
+
+        let mut events = Vec::<Event>::new();
+        // The position of the start of the current row on the whole strip
+        let mut offset_mm = 0f64;
+
+        for (page, document) in documents.iter().enumerate() {
+            let holes = match (
+                ImageReader::is_image(document),
+                std::str::from_utf8(document),
+            ) {
+                (true, _) => self.read_image(document, page, &mut offset_mm)?,
+                (false, Ok(t)) => self.read_svg(t, page, &mut offset_mm)?,
+                (false, Err(_)) => {
+                    return Err(Error::Generic(format!(
+                        "Page {page} is neither an svg, png nor jpeg file."
+                    )))
+                }
+            };
+
+            for (x, index) in holes {
+                let abs = (x * ticks_per_mm).round() as u64;
+                let note = *music_box.get_note(index).res()?;
+                debug!("Found note '{note}' at '{abs}' on page {page}.");
+                events.push(Event::new(note.clone(), abs, VELOCITY));
+            }
+        }
+
+        if events.is_empty() {
+            return Err(Error::Generic(format!(
+                "Found no note holes for the music box '{}'. Check the box name.",
+                music_box.name
+            )));
+        }
+        info!("Decoded {} notes.", events.len());
+
+        events.sort_by_key(|el| (el.abs, el.note.to_midi_pitch().map(|t| t.as_int())));
+        let mut track = Track::new();
+        track.extend(events);
+        self.track = Some(track);
+        Ok(())
+    }
+
+    /// Finds the note holes of an svg file written by the converter. Returns their position on the whole strip and their note index
+    fn read_svg(&self, text: &str, page: usize, offset_mm: &mut f64) -> Result<Vec<(f64, usize)>> {
+        let music_box = self.music_box.res()?;
+        let reader = SvgReader::parse(text)?;
+        let elements = reader.elements();
+        let layered = elements.iter().any(|el| el.layer.is_some());
+
+        let rows = self.find_rows(elements, layered)?;
+        if rows.is_empty() {
+            warn!(
+                "Page {page} contains no strip rows for the music box '{}'. Skipping.",
+                music_box.name
+            );
+            return Ok(Vec::new());
+        }
+
+        // Files written before the layers existed have the sprocket holes in between, but they are never on a staff line
+        let holes = elements
+            .iter()
+            .filter(|el| el.name == "circle")
+            .filter(|el| !layered || el.layer.as_deref() == Some("holes"))
+            .filter_map(|el| Some((el.number("cx")?, el.number("cy")?)))
+            .collect::<Vec<(f64, f64)>>();
+
+        let tolerance = Self::line_tolerance(music_box);
+        let mut output = Vec::<(f64, usize)>::new();
+        for row in rows {
+            for (x, y) in holes.iter() {
+                // Holes in the glue area repeat the end of the row before
+                if *x < row.origin_x - TOLERANCE_MM
+                    || (row.glue && *x < row.origin_x + TOLERANCE_MM)
+                {
+                    continue;
+                }
+                if let Some(t) = Self::note_index(music_box, y - row.staff_y, tolerance) {
+                    output.push((*offset_mm + x - row.origin_x, t));
+                }
+            }
+            *offset_mm += row.end_x - row.origin_x;
+        }
+
+        Ok(output)
+    }
+
+    /// Finds the holes of a scanned or photographed strip. The strip starts at its left edge and has the first note line on top.
+    /// Returns their position on the whole strip and their note index
+    fn read_image(
+        &self,
+        data: &[u8],
+        page: usize,
+        offset_mm: &mut f64,
+    ) -> Result<Vec<(f64, usize)>> {
+        let music_box = self.music_box.res()?;
+        let px_per_mm = match self.args.get_one::<f64>("dpi") {
+            Some(t) if *t > 0f64 => *t / MM_PER_INCH,
+            Some(_) => {
+                return Err(Error::Generic(
+                    "The resolution has to be above zero.".to_string(),
+                ))
+            }
+            None => {
+                return Err(Error::Generic(format!(
+                    "Page {page} is an image. Set its resolution with --dpi."
+                )))
+            }
+        };
+
+        let image = ImageReader::decode(data)?;
+        let threshold = image.threshold();
+        let min_diameter = HOLE_MIN_DIAMETER_MM * px_per_mm;
+        let bounds = match image.bounds(threshold, (min_diameter / 2f64) as usize) {
+            Some(t) => t,
+            None => {
+                warn!("Found no strip on page {page}. Skipping.");
+                return Ok(Vec::new());
+            }
+        };
+        let holes = image.holes(
+            &bounds,
+            threshold,
+            min_diameter,
+            HOLE_MAX_DIAMETER_MM * px_per_mm,
+        );
+        info!(
+            "Found the strip at {bounds:?} and {} holes on page {page}.",
+            holes.len()
+        );
+
+        // Relative to the top left corner of the strip in millimetres
+        let holes = holes
+            .iter()
+            .map(|el| {
+                (
+                    (el.x - bounds.left as f64) / px_per_mm,
+                    (el.y - bounds.top as f64) / px_per_mm,
+                )
+            })
+            .collect::<Vec<(f64, f64)>>();
+
+        let tolerance = Self::line_tolerance(music_box);
+        let staff_y = Self::fit_staff(
+            music_box,
+            &holes,
+            (bounds.bottom - bounds.top) as f64 / px_per_mm,
+            tolerance,
+        );
+        debug!("The top note line is {staff_y}mm below the edge of the strip on page {page}.");
+
+        let output = holes
+            .iter()
+            .filter_map(|(x, y)| {
+                Some((
+                    *offset_mm + x,
+                    Self::note_index(music_box, y - staff_y, tolerance)?,
+                ))
+            })
+            .collect::<Vec<(f64, usize)>>();
+        if output.len() < holes.len() {
+            info!(
+                "Left out {} holes that aren't on a note line on page {page}.",
+                holes.len() - output.len()
+            );
+        }
+        *offset_mm += (bounds.right - bounds.left) as f64 / px_per_mm;
+        Ok(output)
+    }
+
+    /// Finds how far the top note line is below the top edge of the strip by putting as many holes as possible onto note lines.
+    /// Prefers the note lines in the centre of the strip if they fit equally well somewhere else
+    fn fit_staff(music_box: &MusicBox, holes: &[(f64, f64)], height: f64, tolerance: f64) -> f64 {
+        let offsets = (0..music_box.note_count())
+            .map(|el| music_box.note_y_mm(el))
+            .collect::<Vec<f64>>();
+        let top = offsets.iter().copied().fold(f64::MAX, f64::min);
+        let bottom = offsets.iter().copied().fold(f64::MIN, f64::max);
+        let centre = (height - top - bottom) / 2f64;
+
+        // The squared distances to the closest note line. Holes further away than the tolerance count as much as the tolerance
+        let cost = |staff_y: f64| {
+            holes
+                .iter()
+                .map(|(_, y)| {
+                    offsets
+                        .iter()
+                        .map(|el| (y - staff_y - el).abs())
+                        .fold(tolerance, f64::min)
+                        .powi(2)
+                })
+                .sum::<f64>()
+                + FIT_CENTRE_WEIGHT * (staff_y - centre).powi(2)
+        };
+        let best = |candidates: &mut dyn Iterator<Item = f64>| {
+            candidates
+                .map(|el| (el, cost(el)))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map_or(centre, |el| el.0)
+        };
+
+        // The note lines have to be on the strip
+        let (start, end) = ((-top).min(height - bottom), (-top).max(height - bottom));
+        let coarse = best(
+            &mut (0..=((end - start) / FIT_STEP_MM).ceil() as usize)
+                .map(|el| start + el as f64 * FIT_STEP_MM),
+        );
+        best(&mut (-10..=10).map(|el| coarse + el as f64 * FIT_STEP_MM / 10f64))
+    }
+
+    /// A hole belongs to the closest note line if it's closer than halfway to the next one
+    fn line_tolerance(music_box: &MusicBox) -> f64 {
+        let mut offsets = (0..music_box.note_count())
+            .map(|el| music_box.note_y_mm(el))
+            .collect::<Vec<f64>>();
+        offsets.sort_by(f64::total_cmp);
+        offsets
+            .windows(2)
+            .map(|el| el[1] - el[0])
+            .fold(f64::MAX, f64::min)
+            / 2f64
+    }
+
+    /// Returns the index of the note line closest to the given distance below the top note line if it's within the tolerance
+    fn note_index(music_box: &MusicBox, y: f64, tolerance: f64) -> Option<usize> {
+        (0..music_box.note_count())
+            .map(|el| (el, (y - music_box.note_y_mm(el)).abs()))
+            .filter(|el| el.1 < tolerance)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|el| el.0)
+    }
+
+    /// Finds the strip rows of a page by their staff lines. Every staff line of the music box has to be there
+    fn find_rows(&self, elements: &[Element], layered: bool) -> Result<Vec<Row>> {
+        let music_box = self.music_box.res()?;
+        let offsets = (0..music_box.note_count())
+            .map(|el| music_box.note_y_mm(el))
+            .collect::<Vec<f64>>();
+        let top = offsets.iter().copied().fold(f64::MAX, f64::min);
+        let height = offsets.iter().copied().fold(f64::MIN, f64::max) - top;
+
+        // (y, start x, end x) of all horizontal lines
+        let mut lines = elements
+            .iter()
+            .filter(|el| el.name == "line")
+            .filter(|el| !layered || el.layer.as_deref() == Some("staff"))
+            .filter_map(|el| {
+                let (x1, y1, x2, y2) = (
+                    el.number("x1")?,
+                    el.number("y1")?,
+                    el.number("x2")?,
+                    el.number("y2")?,
+                );
+                ((y1 - y2).abs() < TOLERANCE_MM).then_some((y1, x1.min(x2), x1.max(x2)))
+            })
+            .collect::<Vec<(f64, f64, f64)>>();
+        lines.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let glue_areas = elements
+            .iter()
+            .filter(|el| el.name == "rect")
+            .filter(|el| !layered || el.layer.as_deref() == Some("glue"))
+            .filter_map(|el| {
+                Some((
+                    el.number("x")?,
+                    el.number("y")?,
+                    el.number("width")?,
+                    el.number("height")?,
+                ))
+            })
+            .collect::<Vec<(f64, f64, f64, f64)>>();
+
+        let mut rows = Vec::<Row>::new();
+        for (y, start_x, end_x) in lines.iter().copied() {
+            // Lines of the row found last
+            if let Some(t) = rows.last() {
+                if y < t.staff_y + top + height + TOLERANCE_MM {
+                    continue;
+                }
+            }
+
+            let staff_y = y - top;
+            if !offsets.iter().all(|offset| {
+                lines
+                    .iter()
+                    .any(|el| (el.0 - staff_y - offset).abs() < TOLERANCE_MM)
+            }) {
+                continue;
+            }
+
+            let glue = glue_areas
+                .iter()
+                .find(|el| (el.0 - start_x).abs() < TOLERANCE_MM && el.1 <= y && y <= el.1 + el.3);
+            rows.push(Row {
+                staff_y,
+                origin_x: glue.map_or(start_x, |el| el.0 + el.2),
+                end_x,
+                glue: glue.is_some(),
+            });
+        }
+
+        Ok(rows)
+    }
+
+    /// Writes self.track into a midi file
+    fn write_midi(&self) -> Result<()> {
+        let path_string = self.args.get_one::<String>("io_out").unwrap();
+        let abs_path = match crate::path::absolute_path(path_string) {
+            Ok(t) => t,
+            Err(e) => return Err(Error::IOError(Box::new(e), Box::new(path_string.clone()))),
+        };
+        if let Some(t) = abs_path.parent() {
+            std::fs::create_dir_all(t).unwrap_or_default(); // Errors are caught when creating the file
+        }
+
+        let tempo = *self.args.get_one::<f64>("tempo").unwrap();
+        if tempo <= 0f64 {
+            return Err(Error::Generic(
+                "The tempo has to be above zero.".to_string(),
+            ));
+        }
+        let ticks_per_beat = *self.args.get_one::<u16>("ticks_per_beat").unwrap();
+
+        // Track::to_midi_track copies the tempo from this track
+        let template: MidiTrack = vec![TrackEvent {
+            delta: u28::from(0),
+            kind: TrackEventKind::Meta(MetaMessage::Tempo(u24::from(
+                (60_000_000f64 / tempo).round() as u32,
+            ))),
+        }];
+
+        let mut smf = Smf::new(Header::new(
+            Format::SingleTrack,
+            Timing::Metrical(u15::from(ticks_per_beat)),
+        ));
+        smf.tracks.push(self.track.res()?.to_midi_track(template));
+
+        let mut buf = Vec::<u8>::new();
+        if let Err(e) = smf.write(&mut buf) {
+            return Err(Error::Generic(format!("Couldn't write the midi file: {e}")));
+        }
+
+        let mut file = match File::create(&abs_path) {
+            Ok(t) => t,
+            Err(e) => return Err(Error::IOError(Box::new(e), Box::new(path_string.clone()))),
+        };
+        match file.write_all(&buf) {
+            Ok(t) => Ok(t),
+            Err(e) => Err(Error::IOError(Box::new(e), Box::new(path_string.clone()))),
+        }
+    }
+}
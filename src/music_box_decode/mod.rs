@@ -0,0 +1,41 @@
+// Modules
+mod command;
+pub mod functions;
+
+// clap
+use clap::ArgMatches;
+
+// Internal
+use crate::music::{music_box::MusicBox, track::Track};
+
+#[derive(Debug, Default)]
+pub struct MusicBoxDecode {
+    /// The arguments of the program. Need to be passed in with the new method
+    args: ArgMatches,
+    /// The `MusicBox` the strips were made for
+    music_box: Option<MusicBox>,
+    /// The decoded notes
+    track: Option<Track>,
+}
+
+/// A strip row found in an svg file
+#[derive(Debug, Clone, Copy)]
+struct Row {
+    /// The vertical position of the top staff line
+    staff_y: f64,
+    /// The horizontal position where the row continues the row before. Holes in front of it repeat the end of the row before
+    origin_x: f64,
+    /// The horizontal position of the end of the staff
+    end_x: f64,
+    /// Whether the row starts with a glue area. The hole at origin_x is then the last one of the row before
+    glue: bool,
+}
+
+impl MusicBoxDecode {
+    pub fn new(args: &ArgMatches) -> Self {
+        Self {
+            args: args.clone(),
+            ..Default::default()
+        }
+    }
+}
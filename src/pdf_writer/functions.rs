@@ -0,0 +1,355 @@
+// std
+use std::{fs::File, io::Write};
+
+// Internal
+use super::{PdfPage, PdfWriter};
+use crate::{
+    colour::Colour,
+    prelude::*,
+    svg_writer::{document::Document, path::PathCommand, renderer::Renderer, text::TextAnchor},
+    vec2::Vec2,
+};
+
+/// Points per millimetre. Pdf files measure everything in points (1/72 inch)
+const POINTS_PER_MM: f64 = 72f64 / 25.4f64;
+
+/// Distance of the bezier control points from the end points when approximating a quarter circle
+const KAPPA: f64 = 0.552_284_749_8;
+
+/// The fonts every page can use. Helvetica is one of the standard fonts every pdf reader has
+const RESOURCES: &str =
+    "<< /Font << /F1 << /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >> >> >>";
+
+/// The widths of the printable ascii characters in Helvetica in thousandths of the font size. Used to align text
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+impl PdfWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a document as a new page
+    pub fn add_page(&mut self, document: &Document) -> Result<()> {
+        let size = document.size().clone();
+
+        // Flip the y axis and scale to millimetres so we can draw in the same coordinates as the svg
+        let mut page = PdfPage {
+            content: format!(
+                "{0} 0 0 {1} 0 {2} cm\n",
+                number(POINTS_PER_MM),
+                number(-POINTS_PER_MM),
+                number(size.y * POINTS_PER_MM)
+            ),
+            size,
+        };
+
+        document.render(&mut page)?;
+        self.pages.push(page);
+        Ok(())
+    }
+
+    /// Returns the pdf file
+    pub fn print(&self) -> Vec<u8> {
+        let mut objects = Vec::<String>::new();
+
+        // The catalog and the page tree are the first two objects. Every page is followed by its content stream
+        objects.push("<< /Type /Catalog /Pages 2 0 R >>".to_string());
+        let kids = (0..self.pages.len())
+            .map(|el| format!("{} 0 R", 3 + el * 2))
+            .collect::<Vec<String>>()
+            .join(" ");
+        objects.push(format!(
+            "<< /Type /Pages /Kids [{kids}] /Count {} >>",
+            self.pages.len()
+        ));
+
+        for (i, page) in self.pages.iter().enumerate() {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {0} {1}] /Contents {2} 0 R /Resources {RESOURCES} >>",
+                number(page.size.x * POINTS_PER_MM),
+                number(page.size.y * POINTS_PER_MM),
+                4 + i * 2
+            ));
+            objects.push(format!(
+                "<< /Length {0} >>\nstream\n{1}endstream",
+                page.content.len(),
+                page.content
+            ));
+        }
+
+        let mut out = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::<usize>::new();
+
+        for (i, object) in objects.iter().enumerate() {
+            offsets.push(out.len());
+            out.extend_from_slice(format!("{} 0 obj\n{object}\nendobj\n", i + 1).as_bytes());
+        }
+
+        let xref = out.len();
+        out.extend_from_slice(
+            format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+        );
+        for offset in offsets {
+            out.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+        }
+        out.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {0} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+                objects.len() + 1
+            )
+            .as_bytes(),
+        );
+
+        out
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> Result<()> {
+        let mut file = match File::create(path) {
+            Ok(t) => t,
+            Err(e) => {
+                return Err(Error::IOError(
+                    Box::new(e),
+                    Box::new(path.to_string_lossy().to_string()),
+                ))
+            }
+        };
+
+        match file.write_all(&self.print()) {
+            Ok(t) => (),
+            Err(e) => {
+                return Err(Error::IOError(
+                    Box::new(e),
+                    Box::new(path.to_string_lossy().to_string()),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Renderer for PdfPage {
+    fn line(
+        &mut self,
+        start: Vec2<f64>,
+        end: Vec2<f64>,
+        stroke: &str,
+        stroke_width: f64,
+    ) -> Result<()> {
+        let (r, g, b) = stroke.parse::<Colour>()?.to_unit();
+        self.content += &format!(
+            "{0} {1} {2} RG {3} w {4} {5} m {6} {7} l S\n",
+            number(r),
+            number(g),
+            number(b),
+            number(stroke_width),
+            number(start.x),
+            number(start.y),
+            number(end.x),
+            number(end.y)
+        );
+        Ok(())
+    }
+
+    fn circle(&mut self, centre: Vec2<f64>, radius: f64, fill: &str) -> Result<()> {
+        let (r, g, b) = fill.parse::<Colour>()?.to_unit();
+        let (x, y) = (centre.x, centre.y);
+        let k = radius * KAPPA;
+
+        self.content += &format!("{0} {1} {2} rg ", number(r), number(g), number(b));
+        self.content += &format!("{0} {1} m ", number(x + radius), number(y));
+        // Four quarter circles, each given as two control points and an end point
+        for points in [
+            [x + radius, y + k, x + k, y + radius, x, y + radius],
+            [x - k, y + radius, x - radius, y + k, x - radius, y],
+            [x - radius, y - k, x - k, y - radius, x, y - radius],
+            [x + k, y - radius, x + radius, y - k, x + radius, y],
+        ] {
+            self.content += &points
+                .iter()
+                .map(|el| number(*el))
+                .collect::<Vec<String>>()
+                .join(" ");
+            self.content += " c ";
+        }
+        self.content += "f\n";
+        Ok(())
+    }
+
+    fn text(
+        &mut self,
+        position: Vec2<f64>,
+        text: &str,
+        font_size: f64,
+        fill: &str,
+        anchor: TextAnchor,
+    ) -> Result<()> {
+        let (r, g, b) = fill.parse::<Colour>()?.to_unit();
+        let text = to_win_ansi(text);
+        let width = text
+            .chars()
+            .map(|el| HELVETICA_WIDTHS[el as usize - 32] as f64 / 1000f64 * font_size)
+            .sum::<f64>();
+        let x = match anchor {
+            TextAnchor::Start => position.x,
+            TextAnchor::Middle => position.x - width / 2f64,
+            TextAnchor::End => position.x - width,
+        };
+
+        // The text matrix flips the y axis back, otherwise the text would be upside down
+        self.content += &format!(
+            "BT /F1 {0} Tf {1} {2} {3} rg 1 0 0 -1 {4} {5} Tm ({6}) Tj ET\n",
+            number(font_size),
+            number(r),
+            number(g),
+            number(b),
+            number(x),
+            number(position.y),
+            text.replace('\\', "\\\\")
+                .replace('(', "\\(")
+                .replace(')', "\\)")
+        );
+        Ok(())
+    }
+
+    fn path(
+        &mut self,
+        commands: &[PathCommand<f64>],
+        fill: Option<&str>,
+        stroke: Option<&str>,
+        stroke_width: f64,
+    ) -> Result<()> {
+        if let Some(t) = fill {
+            let (r, g, b) = t.parse::<Colour>()?.to_unit();
+            self.content += &format!("{0} {1} {2} rg ", number(r), number(g), number(b));
+        }
+        if let Some(t) = stroke {
+            let (r, g, b) = t.parse::<Colour>()?.to_unit();
+            self.content += &format!(
+                "{0} {1} {2} RG {3} w ",
+                number(r),
+                number(g),
+                number(b),
+                number(stroke_width)
+            );
+        }
+
+        for command in commands {
+            self.content += &match command {
+                PathCommand::MoveTo(t) => format!("{0} {1} m ", number(t.x), number(t.y)),
+                PathCommand::LineTo(t) => format!("{0} {1} l ", number(t.x), number(t.y)),
+                PathCommand::Close => "h ".to_string(),
+            };
+        }
+
+        self.content += match (fill, stroke) {
+            (Some(_), Some(_)) => "B\n",
+            (Some(_), None) => "f\n",
+            (None, Some(_)) => "S\n",
+            (None, None) => "n\n",
+        };
+        Ok(())
+    }
+}
+
+/// Replaces characters Helvetica can't show with printable ascii characters
+fn to_win_ansi(text: &str) -> String {
+    text.chars()
+        .map(|el| match el {
+            ' '..='~' => el,
+            '♯' => '#',
+            '♭' => 'b',
+            _ => '?',
+        })
+        .collect()
+}
+
+/// Formats a number for a pdf file. Pdf doesn't allow exponents and a few decimals are plenty for printing
+fn number(value: f64) -> String {
+    let formatted = format!("{value:.4}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" | "" => "0".to_string(),
+        _ => trimmed.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg_writer::{circle::Circle, document::Layer, line::Line, text::Text};
+
+    #[test]
+    fn print() {
+        let mut document = Document::new(297f64, 210f64);
+        document.append(
+            Layer::Decoration,
+            Line::new_builder()
+                .set_start(10f64, 10f64)
+                .set_end(20f64, 10f64)
+                .set_stroke("#000000".to_string())
+                .set_stroke_width(1f64)
+                .finish(),
+        );
+        document.append(
+            Layer::Decoration,
+            Circle::new_builder()
+                .set_centre(15f64, 12.5f64)
+                .set_radius(1f64)
+                .set_fill("#ff0000".to_string())
+                .finish(),
+        );
+
+        let mut pdf = PdfWriter::new();
+        pdf.add_page(&document).unwrap();
+        pdf.add_page(&Document::new(100f64, 50f64)).unwrap();
+        let bytes = pdf.print();
+        let out = String::from_utf8_lossy(&bytes).to_string();
+
+        assert!(out.starts_with("%PDF-1.4"));
+        assert!(out.contains("/Count 2"));
+        assert!(out.contains("/MediaBox [0 0 841.8898 595.2756]"));
+        assert!(out.contains("0 0 0 RG 1 w 10 10 m 20 10 l S"));
+        assert!(out.contains("1 0 0 rg 16 12.5 m"));
+
+        // The cross-reference table has to point at the objects
+        let startxref = out.lines().rev().nth(1).unwrap().parse::<usize>().unwrap();
+        assert!(bytes[startxref..].starts_with(b"xref"));
+        let first_object = bytes.windows(7).position(|el| el == b"1 0 obj").unwrap();
+        assert!(out.contains(&format!("{first_object:010} 00000 n ")));
+
+        let mut invalid = Document::default();
+        invalid.append(
+            Layer::Holes,
+            Circle::new_builder()
+                .set_centre(0f64, 0f64)
+                .set_radius(1f64)
+                .set_fill("not a colour".to_string())
+                .finish(),
+        );
+        assert!(PdfWriter::new().add_page(&invalid).is_err());
+
+        let mut text = Document::default();
+        text.append(
+            Layer::Decoration,
+            Text::new_builder()
+                .set_position(100f64, 20f64)
+                .set_text("F♯3 (1)".to_string())
+                .set_font_size(10f64)
+                .set_fill("#000000".to_string())
+                .set_anchor(TextAnchor::End)
+                .finish(),
+        );
+        let mut pdf = PdfWriter::new();
+        pdf.add_page(&text).unwrap();
+        let out = String::from_utf8_lossy(&pdf.print()).to_string();
+        // 'F#3 (1)' is 611 + 556 + 556 + 278 + 333 + 556 + 333 thousandths wide
+        assert!(out.contains("1 0 0 -1 67.77 20 Tm (F#3 \\(1\\)) Tj"));
+    }
+}
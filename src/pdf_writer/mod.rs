@@ -0,0 +1,20 @@
+// Modules
+pub mod functions;
+
+// Internal
+use crate::vec2::Vec2;
+
+/// Writes [crate::svg_writer::document::Document]s into a single pdf file with one page per document
+#[derive(Debug, Default)]
+pub struct PdfWriter {
+    pages: Vec<PdfPage>,
+}
+
+/// A single page of a pdf file. Implements [crate::svg_writer::renderer::Renderer] to collect the drawing operators of a document
+#[derive(Debug, Default)]
+pub struct PdfPage {
+    /// The page size in millimetres
+    size: Vec2<f64>,
+    /// The content stream of the page
+    content: String,
+}
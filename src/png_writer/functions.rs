@@ -0,0 +1,315 @@
+// std
+use std::{fs::File, io::BufWriter};
+
+// Internal
+use super::PngWriter;
+use crate::{
+    colour::Colour,
+    prelude::*,
+    svg_writer::{document::Document, path::PathCommand, renderer::Renderer, text::TextAnchor},
+    vec2::Vec2,
+};
+
+const MM_PER_INCH: f64 = 25.4f64;
+
+impl PngWriter {
+    /// Renders a document at the given resolution. The background is white
+    pub fn from_document(document: &Document, dpi: f64) -> Result<Self> {
+        if dpi <= 0f64 {
+            return Err(Error::Generic(
+                "The dpi have to be greater than zero.".to_string(),
+            ));
+        }
+
+        let width = (document.size().x * dpi / MM_PER_INCH).ceil() as u32;
+        let height = (document.size().y * dpi / MM_PER_INCH).ceil() as u32;
+
+        let mut writer = Self {
+            width,
+            height,
+            dpi,
+            pixels: vec![255u8; width as usize * height as usize * 3],
+        };
+        document.render(&mut writer)?;
+        Ok(writer)
+    }
+
+    /// Returns the png file
+    pub fn print(&self) -> Result<Vec<u8>> {
+        let mut out = Vec::<u8>::new();
+
+        let mut encoder = png::Encoder::new(&mut out, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        // Store the resolution so the image prints at its real size
+        let pixels_per_metre = (self.dpi / MM_PER_INCH * 1000f64).round() as u32;
+        encoder.set_pixel_dims(Some(png::PixelDimensions {
+            xppu: pixels_per_metre,
+            yppu: pixels_per_metre,
+            unit: png::Unit::Meter,
+        }));
+
+        let png_error = |e: png::EncodingError| Error::Generic(format!("Couldn't encode png: {e}"));
+        let mut writer = encoder.write_header().map_err(png_error)?;
+        writer.write_image_data(&self.pixels).map_err(png_error)?;
+        writer.finish().map_err(png_error)?;
+
+        Ok(out)
+    }
+
+    pub fn save(&self, path: &std::path::Path) -> Result<()> {
+        match std::fs::write(path, self.print()?) {
+            Ok(t) => Ok(t),
+            Err(e) => Err(Error::IOError(
+                Box::new(e),
+                Box::new(path.to_string_lossy().to_string()),
+            )),
+        }
+    }
+
+    /// Returns the number of pixels per millimetre
+    fn scale(&self) -> f64 {
+        self.dpi / MM_PER_INCH
+    }
+
+    /// Calls `inside` with the centre of every pixel within the given bounds (in pixels) and colours the pixel if it returns true
+    fn fill<F: Fn(f64, f64) -> bool>(
+        &mut self,
+        min: Vec2<f64>,
+        max: Vec2<f64>,
+        colour: Colour,
+        inside: F,
+    ) {
+        let x_start = min.x.floor().max(0f64) as u32;
+        let y_start = min.y.floor().max(0f64) as u32;
+        let x_end = (max.x.ceil().max(0f64) as u32).min(self.width);
+        let y_end = (max.y.ceil().max(0f64) as u32).min(self.height);
+
+        for y in y_start..y_end {
+            for x in x_start..x_end {
+                if inside(x as f64 + 0.5f64, y as f64 + 0.5f64) {
+                    let i = (y as usize * self.width as usize + x as usize) * 3;
+                    self.pixels[i] = colour.r;
+                    self.pixels[i + 1] = colour.g;
+                    self.pixels[i + 2] = colour.b;
+                }
+            }
+        }
+    }
+}
+
+impl Renderer for PngWriter {
+    /// Draws a line with flat ends like svg does. Lines are at least one pixel wide
+    fn line(
+        &mut self,
+        start: Vec2<f64>,
+        end: Vec2<f64>,
+        stroke: &str,
+        stroke_width: f64,
+    ) -> Result<()> {
+        let colour = stroke.parse::<Colour>()?;
+        let scale = self.scale();
+        let (ax, ay) = (start.x * scale, start.y * scale);
+        let (bx, by) = (end.x * scale, end.y * scale);
+        let half = (stroke_width * scale / 2f64).max(0.5f64);
+
+        let (dx, dy) = (bx - ax, by - ay);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length == 0f64 {
+            return Ok(());
+        }
+
+        self.fill(
+            Vec2::new(ax.min(bx) - half, ay.min(by) - half),
+            Vec2::new(ax.max(bx) + half, ay.max(by) + half),
+            colour,
+            |x, y| {
+                // Position along the line and distance from it
+                let along = ((x - ax) * dx + (y - ay) * dy) / length;
+                let across = ((x - ax) * dy - (y - ay) * dx).abs() / length;
+                (0f64..=length).contains(&along) && across <= half
+            },
+        );
+        Ok(())
+    }
+
+    /// Draws a filled circle. Circles are at least one pixel wide
+    fn circle(&mut self, centre: Vec2<f64>, radius: f64, fill: &str) -> Result<()> {
+        let colour = fill.parse::<Colour>()?;
+        let scale = self.scale();
+        let (cx, cy) = (centre.x * scale, centre.y * scale);
+        let r = (radius * scale).max(0.5f64);
+
+        self.fill(
+            Vec2::new(cx - r, cy - r),
+            Vec2::new(cx + r, cy + r),
+            colour,
+            |x, y| (x - cx).powi(2) + (y - cy).powi(2) <= r * r,
+        );
+        Ok(())
+    }
+
+    /// There is no font rasterizer so text is left out of the preview
+    fn text(
+        &mut self,
+        position: Vec2<f64>,
+        text: &str,
+        font_size: f64,
+        fill: &str,
+        anchor: TextAnchor,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Fills the path with the even-odd rule and strokes its outline
+    fn path(
+        &mut self,
+        commands: &[PathCommand<f64>],
+        fill: Option<&str>,
+        stroke: Option<&str>,
+        stroke_width: f64,
+    ) -> Result<()> {
+        // Split the path into its edges. Fills close every sub path implicitly
+        let mut edges = Vec::<(Vec2<f64>, Vec2<f64>)>::new();
+        let mut fill_edges = Vec::<(Vec2<f64>, Vec2<f64>)>::new();
+        let mut start = Vec2::new(0f64, 0f64);
+        let mut current = Vec2::new(0f64, 0f64);
+        for command in commands
+            .iter()
+            .chain([PathCommand::MoveTo(Vec2::new(0f64, 0f64))].iter())
+        {
+            match command {
+                PathCommand::MoveTo(t) => {
+                    fill_edges.push((current, start.clone()));
+                    start = t.clone();
+                    current = t.clone();
+                }
+                PathCommand::LineTo(t) => {
+                    edges.push((current.clone(), t.clone()));
+                    fill_edges.push((current, t.clone()));
+                    current = t.clone();
+                }
+                PathCommand::Close => {
+                    edges.push((current.clone(), start.clone()));
+                    fill_edges.push((current, start.clone()));
+                    current = start.clone();
+                }
+            }
+        }
+
+        fill_edges.retain(|(a, b)| a.x != b.x || a.y != b.y);
+
+        if let Some(t) = fill {
+            let colour = t.parse::<Colour>()?;
+            let scale = self.scale();
+            let scaled = fill_edges
+                .iter()
+                .map(|(a, b)| (a.x * scale, a.y * scale, b.x * scale, b.y * scale))
+                .collect::<Vec<(f64, f64, f64, f64)>>();
+
+            let min = Vec2::new(
+                scaled
+                    .iter()
+                    .map(|el| el.0.min(el.2))
+                    .fold(f64::MAX, f64::min),
+                scaled
+                    .iter()
+                    .map(|el| el.1.min(el.3))
+                    .fold(f64::MAX, f64::min),
+            );
+            let max = Vec2::new(
+                scaled
+                    .iter()
+                    .map(|el| el.0.max(el.2))
+                    .fold(f64::MIN, f64::max),
+                scaled
+                    .iter()
+                    .map(|el| el.1.max(el.3))
+                    .fold(f64::MIN, f64::max),
+            );
+
+            self.fill(min, max, colour, |x, y| {
+                // Count the edges a ray to the right crosses
+                scaled
+                    .iter()
+                    .filter(|(ax, ay, bx, by)| {
+                        (*ay > y) != (*by > y) && x < ax + (y - ay) / (by - ay) * (bx - ax)
+                    })
+                    .count()
+                    % 2
+                    == 1
+            });
+        }
+
+        if let Some(t) = stroke {
+            for (a, b) in edges {
+                self.line(a, b, t, stroke_width)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg_writer::{circle::Circle, document::Layer, line::Line, path::Path};
+
+    #[test]
+    fn rasterize() {
+        // 10mm x 5mm at 254 dpi is 100 x 50 pixels
+        let mut document = Document::new(10f64, 5f64);
+        document.append(
+            Layer::Staff,
+            Line::new_builder()
+                .set_start(1f64, 1f64)
+                .set_end(9f64, 1f64)
+                .set_stroke("#0000ff".to_string())
+                .set_stroke_width(0.2f64)
+                .finish(),
+        );
+        document.append(
+            Layer::Holes,
+            Circle::new_builder()
+                .set_centre(5f64, 3f64)
+                .set_radius(1f64)
+                .set_fill("#ff0000".to_string())
+                .finish(),
+        );
+
+        let writer = PngWriter::from_document(&document, 254f64).unwrap();
+        assert_eq!((writer.width, writer.height), (100, 50));
+
+        let pixel = |x: usize, y: usize| {
+            let i = (y * 100 + x) * 3;
+            (writer.pixels[i], writer.pixels[i + 1], writer.pixels[i + 2])
+        };
+        assert_eq!(pixel(50, 10), (0, 0, 255));
+        assert_eq!(pixel(50, 12), (255, 255, 255));
+        // Flat ends
+        assert_eq!(pixel(9, 10), (255, 255, 255));
+        assert_eq!(pixel(50, 30), (255, 0, 0));
+        assert_eq!(pixel(50, 41), (255, 255, 255));
+
+        // A triangle pointing right with its tip at the right edge
+        let mut document = Document::new(10f64, 5f64);
+        document.append(
+            Layer::Decoration,
+            Path::new_builder()
+                .move_to(6f64, 1f64)
+                .line_to(10f64, 2.5f64)
+                .line_to(6f64, 4f64)
+                .set_fill("#00ff00".to_string())
+                .finish(),
+        );
+        let triangle = PngWriter::from_document(&document, 254f64).unwrap();
+        let pixel = |x: usize, y: usize| triangle.pixels[(y * 100 + x) * 3];
+        assert_eq!(pixel(65, 25), 0);
+        assert_eq!(pixel(95, 25), 0);
+        assert_eq!(pixel(95, 15), 255);
+        assert_eq!(pixel(55, 25), 255);
+
+        let png = writer.print().unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+    }
+}
@@ -0,0 +1,15 @@
+// Modules
+pub mod functions;
+
+/// Renders a [crate::svg_writer::document::Document] into a png image with a small software rasterizer
+#[derive(Debug, Default)]
+pub struct PngWriter {
+    /// The image width in pixels
+    width: u32,
+    /// The image height in pixels
+    height: u32,
+    /// The resolution in dots per inch
+    dpi: f64,
+    /// The rgb pixels, row by row
+    pixels: Vec<u8>,
+}
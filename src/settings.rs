@@ -36,6 +36,86 @@ pub struct Settings {
     pub sprocket_hole_distance_mm: f64,
     pub sprocket_hole_distance_staff_mm: f64,
     pub sprocket_hole_colour: String,
+
+    // Row stacking
+    #[serde(default)]
+    pub row_stacking_enable: bool,
+    #[serde(default)]
+    pub row_gap_mm: f64,
+
+    // G-code
+    #[serde(default = "default_gcode_travel_feed")]
+    pub gcode_travel_feed_mm_min: f64,
+    #[serde(default = "default_gcode_plunge_feed")]
+    pub gcode_plunge_feed_mm_min: f64,
+    #[serde(default = "default_gcode_safe_z")]
+    pub gcode_safe_z_mm: f64,
+    #[serde(default = "default_gcode_punch_z")]
+    pub gcode_punch_z_mm: f64,
+    #[serde(default)]
+    pub gcode_origin_x_mm: f64,
+    #[serde(default)]
+    pub gcode_origin_y_mm: f64,
+
+    // Cut line
+    #[serde(default = "default_cut_line_enable")]
+    pub cut_line_enable: bool,
+    #[serde(default = "default_cut_line_distance")]
+    pub cut_line_distance_mm: f64,
+    #[serde(default = "default_cut_line_thickness")]
+    pub cut_line_thickness_mm: f64,
+    #[serde(default = "default_cut_line_colour")]
+    pub cut_line_colour: String,
+
+    // Note labels
+    #[serde(default)]
+    pub note_label_enable: bool,
+    #[serde(default = "default_note_label_font_size")]
+    pub note_label_font_size_mm: f64,
+    #[serde(default = "default_note_label_colour")]
+    pub note_label_colour: String,
+
+    // Bar lines
+    #[serde(default)]
+    pub bar_line_enable: bool,
+    #[serde(default = "default_bar_line_thickness")]
+    pub bar_line_thickness_mm: f64,
+    #[serde(default = "default_bar_line_colour")]
+    pub bar_line_colour: String,
+    #[serde(default)]
+    pub beat_line_enable: bool,
+    #[serde(default = "default_beat_line_thickness")]
+    pub beat_line_thickness_mm: f64,
+    #[serde(default = "default_beat_line_colour")]
+    pub beat_line_colour: String,
+    #[serde(default)]
+    pub bar_number_enable: bool,
+    #[serde(default = "default_bar_number_font_size")]
+    pub bar_number_font_size_mm: f64,
+
+    // Page header
+    #[serde(default = "default_page_header_enable")]
+    pub page_header_enable: bool,
+    #[serde(default = "default_page_header_font_size")]
+    pub page_header_font_size_mm: f64,
+    #[serde(default = "default_page_header_colour")]
+    pub page_header_colour: String,
+
+    // Continuation marks
+    #[serde(default = "default_continuation_mark_enable")]
+    pub continuation_mark_enable: bool,
+    #[serde(default = "default_continuation_mark_size")]
+    pub continuation_mark_size_mm: f64,
+    #[serde(default = "default_continuation_mark_colour")]
+    pub continuation_mark_colour: String,
+    #[serde(default = "default_continuation_mark_font_size")]
+    pub continuation_mark_font_size_mm: f64,
+
+    // Glue overlap
+    #[serde(default = "default_glue_overlap")]
+    pub glue_overlap_mm: f64,
+    #[serde(default = "default_glue_colour")]
+    pub glue_colour: String,
 }
 
 impl Settings {
@@ -63,6 +143,46 @@ impl Settings {
             18 => self.sprocket_hole_distance_mm = val.self_to_f64().unwrap(),
             19 => self.sprocket_hole_distance_staff_mm = val.self_to_f64().unwrap(),
             20 => self.sprocket_hole_colour = val.self_to_string().unwrap(),
+            // Row stacking
+            22 => self.row_stacking_enable = val.self_to_bool().unwrap(),
+            23 => self.row_gap_mm = val.self_to_f64().unwrap(),
+            // G-code
+            25 => self.gcode_travel_feed_mm_min = val.self_to_f64().unwrap(),
+            26 => self.gcode_plunge_feed_mm_min = val.self_to_f64().unwrap(),
+            27 => self.gcode_safe_z_mm = val.self_to_f64().unwrap(),
+            28 => self.gcode_punch_z_mm = val.self_to_f64().unwrap(),
+            29 => self.gcode_origin_x_mm = val.self_to_f64().unwrap(),
+            30 => self.gcode_origin_y_mm = val.self_to_f64().unwrap(),
+            // Cut line
+            32 => self.cut_line_enable = val.self_to_bool().unwrap(),
+            33 => self.cut_line_distance_mm = val.self_to_f64().unwrap(),
+            34 => self.cut_line_thickness_mm = val.self_to_f64().unwrap(),
+            35 => self.cut_line_colour = val.self_to_string().unwrap(),
+            // Note labels
+            37 => self.note_label_enable = val.self_to_bool().unwrap(),
+            38 => self.note_label_font_size_mm = val.self_to_f64().unwrap(),
+            39 => self.note_label_colour = val.self_to_string().unwrap(),
+            // Bar lines
+            41 => self.bar_line_enable = val.self_to_bool().unwrap(),
+            42 => self.bar_line_thickness_mm = val.self_to_f64().unwrap(),
+            43 => self.bar_line_colour = val.self_to_string().unwrap(),
+            44 => self.beat_line_enable = val.self_to_bool().unwrap(),
+            45 => self.beat_line_thickness_mm = val.self_to_f64().unwrap(),
+            46 => self.beat_line_colour = val.self_to_string().unwrap(),
+            47 => self.bar_number_enable = val.self_to_bool().unwrap(),
+            48 => self.bar_number_font_size_mm = val.self_to_f64().unwrap(),
+            // Page header
+            50 => self.page_header_enable = val.self_to_bool().unwrap(),
+            51 => self.page_header_font_size_mm = val.self_to_f64().unwrap(),
+            52 => self.page_header_colour = val.self_to_string().unwrap(),
+            // Continuation marks
+            54 => self.continuation_mark_enable = val.self_to_bool().unwrap(),
+            55 => self.continuation_mark_size_mm = val.self_to_f64().unwrap(),
+            56 => self.continuation_mark_colour = val.self_to_string().unwrap(),
+            57 => self.continuation_mark_font_size_mm = val.self_to_f64().unwrap(),
+            // Glue overlap
+            59 => self.glue_overlap_mm = val.self_to_f64().unwrap(),
+            60 => self.glue_colour = val.self_to_string().unwrap(),
             _ => (),
         }
     }
@@ -97,6 +217,48 @@ impl Settings {
             18 => Some(ValueWrapper::from_f64(self.sprocket_hole_distance_mm)),
             19 => Some(ValueWrapper::from_f64(self.sprocket_hole_distance_staff_mm)),
             20 => Some(ValueWrapper::from_string(self.sprocket_hole_colour.clone())),
+            // Row stacking
+            22 => Some(ValueWrapper::from_bool(self.row_stacking_enable)),
+            23 => Some(ValueWrapper::from_f64(self.row_gap_mm)),
+            // G-code
+            25 => Some(ValueWrapper::from_f64(self.gcode_travel_feed_mm_min)),
+            26 => Some(ValueWrapper::from_f64(self.gcode_plunge_feed_mm_min)),
+            27 => Some(ValueWrapper::from_f64(self.gcode_safe_z_mm)),
+            28 => Some(ValueWrapper::from_f64(self.gcode_punch_z_mm)),
+            29 => Some(ValueWrapper::from_f64(self.gcode_origin_x_mm)),
+            30 => Some(ValueWrapper::from_f64(self.gcode_origin_y_mm)),
+            // Cut line
+            32 => Some(ValueWrapper::from_bool(self.cut_line_enable)),
+            33 => Some(ValueWrapper::from_f64(self.cut_line_distance_mm)),
+            34 => Some(ValueWrapper::from_f64(self.cut_line_thickness_mm)),
+            35 => Some(ValueWrapper::from_string(self.cut_line_colour.clone())),
+            // Note labels
+            37 => Some(ValueWrapper::from_bool(self.note_label_enable)),
+            38 => Some(ValueWrapper::from_f64(self.note_label_font_size_mm)),
+            39 => Some(ValueWrapper::from_string(self.note_label_colour.clone())),
+            // Bar lines
+            41 => Some(ValueWrapper::from_bool(self.bar_line_enable)),
+            42 => Some(ValueWrapper::from_f64(self.bar_line_thickness_mm)),
+            43 => Some(ValueWrapper::from_string(self.bar_line_colour.clone())),
+            44 => Some(ValueWrapper::from_bool(self.beat_line_enable)),
+            45 => Some(ValueWrapper::from_f64(self.beat_line_thickness_mm)),
+            46 => Some(ValueWrapper::from_string(self.beat_line_colour.clone())),
+            47 => Some(ValueWrapper::from_bool(self.bar_number_enable)),
+            48 => Some(ValueWrapper::from_f64(self.bar_number_font_size_mm)),
+            // Page header
+            50 => Some(ValueWrapper::from_bool(self.page_header_enable)),
+            51 => Some(ValueWrapper::from_f64(self.page_header_font_size_mm)),
+            52 => Some(ValueWrapper::from_string(self.page_header_colour.clone())),
+            // Continuation marks
+            54 => Some(ValueWrapper::from_bool(self.continuation_mark_enable)),
+            55 => Some(ValueWrapper::from_f64(self.continuation_mark_size_mm)),
+            56 => Some(ValueWrapper::from_string(
+                self.continuation_mark_colour.clone(),
+            )),
+            57 => Some(ValueWrapper::from_f64(self.continuation_mark_font_size_mm)),
+            // Glue overlap
+            59 => Some(ValueWrapper::from_f64(self.glue_overlap_mm)),
+            60 => Some(ValueWrapper::from_string(self.glue_colour.clone())),
             _ => None,
         }
     }
@@ -118,6 +280,38 @@ impl Settings {
         sprocket_hole_distance_mm,
         sprocket_hole_distance_staff_mm,
         sprocket_hole_colour,
+        row_stacking_enable,
+        row_gap_mm,
+        gcode_travel_feed_mm_min,
+        gcode_plunge_feed_mm_min,
+        gcode_safe_z_mm,
+        gcode_punch_z_mm,
+        gcode_origin_x_mm,
+        gcode_origin_y_mm,
+        cut_line_enable,
+        cut_line_distance_mm,
+        cut_line_thickness_mm,
+        cut_line_colour,
+        note_label_enable,
+        note_label_font_size_mm,
+        note_label_colour,
+        bar_line_enable,
+        bar_line_thickness_mm,
+        bar_line_colour,
+        beat_line_enable,
+        beat_line_thickness_mm,
+        beat_line_colour,
+        bar_number_enable,
+        bar_number_font_size_mm,
+        page_header_enable,
+        page_header_font_size_mm,
+        page_header_colour,
+        continuation_mark_enable,
+        continuation_mark_size_mm,
+        continuation_mark_colour,
+        continuation_mark_font_size_mm,
+        glue_overlap_mm,
+        glue_colour,
     );
 
     // To add a new group with new items
@@ -228,6 +422,284 @@ impl Settings {
         ValueType::Colour,
         HELP_SPROCKET_HOLE_COLOUR;
     );
+
+    // Row stacking
+    config_macro_add_item!(
+        "Row stacking",
+        HELP_ROW_STACKING_GROUP;
+        row_stacking_enable,
+        "Row stacking enable",
+        ValueType::Boolean,
+        HELP_ROW_STACKING_ENABLE;
+        row_gap_mm,
+        "Row gap (mm)",
+        ValueType::Number,
+        HELP_ROW_GAP;
+    );
+
+    // G-code
+    config_macro_add_item!(
+        "G-code",
+        HELP_GCODE_GROUP;
+        gcode_travel_feed_mm_min,
+        "Travel feed rate (mm/min)",
+        ValueType::Number,
+        HELP_GCODE_TRAVEL_FEED;
+        gcode_plunge_feed_mm_min,
+        "Plunge feed rate (mm/min)",
+        ValueType::Number,
+        HELP_GCODE_PLUNGE_FEED;
+        gcode_safe_z_mm,
+        "Safe Z height (mm)",
+        ValueType::Number,
+        HELP_GCODE_SAFE_Z;
+        gcode_punch_z_mm,
+        "Punch Z height (mm)",
+        ValueType::Number,
+        HELP_GCODE_PUNCH_Z;
+        gcode_origin_x_mm,
+        "Origin X offset (mm)",
+        ValueType::Number,
+        HELP_GCODE_ORIGIN_X;
+        gcode_origin_y_mm,
+        "Origin Y offset (mm)",
+        ValueType::Number,
+        HELP_GCODE_ORIGIN_Y;
+    );
+
+    // Cut line
+    config_macro_add_item!(
+        "Cut line",
+        HELP_CUT_LINE_GROUP;
+        cut_line_enable,
+        "Cut line enable",
+        ValueType::Boolean,
+        HELP_CUT_LINE_ENABLE;
+        cut_line_distance_mm,
+        "Cut line distance to staff (mm)",
+        ValueType::Number,
+        HELP_CUT_LINE_DISTANCE;
+        cut_line_thickness_mm,
+        "Cut line thickness (mm)",
+        ValueType::Number,
+        HELP_CUT_LINE_THICKNESS;
+        cut_line_colour,
+        "Cut line colour",
+        ValueType::Colour,
+        HELP_CUT_LINE_COLOUR;
+    );
+
+    // Note labels
+    config_macro_add_item!(
+        "Note labels",
+        HELP_NOTE_LABEL_GROUP;
+        note_label_enable,
+        "Note labels enable",
+        ValueType::Boolean,
+        HELP_NOTE_LABEL_ENABLE;
+        note_label_font_size_mm,
+        "Note label font size (mm)",
+        ValueType::Number,
+        HELP_NOTE_LABEL_FONT_SIZE;
+        note_label_colour,
+        "Note label colour",
+        ValueType::Colour,
+        HELP_NOTE_LABEL_COLOUR;
+    );
+
+    // Bar lines
+    config_macro_add_item!(
+        "Bar lines",
+        HELP_BAR_LINE_GROUP;
+        bar_line_enable,
+        "Bar lines enable",
+        ValueType::Boolean,
+        HELP_BAR_LINE_ENABLE;
+        bar_line_thickness_mm,
+        "Bar line thickness (mm)",
+        ValueType::Number,
+        HELP_BAR_LINE_THICKNESS;
+        bar_line_colour,
+        "Bar line colour",
+        ValueType::Colour,
+        HELP_BAR_LINE_COLOUR;
+        beat_line_enable,
+        "Beat lines enable",
+        ValueType::Boolean,
+        HELP_BEAT_LINE_ENABLE;
+        beat_line_thickness_mm,
+        "Beat line thickness (mm)",
+        ValueType::Number,
+        HELP_BEAT_LINE_THICKNESS;
+        beat_line_colour,
+        "Beat line colour",
+        ValueType::Colour,
+        HELP_BEAT_LINE_COLOUR;
+        bar_number_enable,
+        "Bar numbers enable",
+        ValueType::Boolean,
+        HELP_BAR_NUMBER_ENABLE;
+        bar_number_font_size_mm,
+        "Bar number font size (mm)",
+        ValueType::Number,
+        HELP_BAR_NUMBER_FONT_SIZE;
+    );
+
+    // Page header
+    config_macro_add_item!(
+        "Page header",
+        HELP_PAGE_HEADER_GROUP;
+        page_header_enable,
+        "Page header enable",
+        ValueType::Boolean,
+        HELP_PAGE_HEADER_ENABLE;
+        page_header_font_size_mm,
+        "Page header font size (mm)",
+        ValueType::Number,
+        HELP_PAGE_HEADER_FONT_SIZE;
+        page_header_colour,
+        "Page header colour",
+        ValueType::Colour,
+        HELP_PAGE_HEADER_COLOUR;
+    );
+
+    // Continuation marks
+    config_macro_add_item!(
+        "Continuation marks",
+        HELP_CONTINUATION_MARK_GROUP;
+        continuation_mark_enable,
+        "Continuation marks enable",
+        ValueType::Boolean,
+        HELP_CONTINUATION_MARK_ENABLE;
+        continuation_mark_size_mm,
+        "Continuation mark size (mm)",
+        ValueType::Number,
+        HELP_CONTINUATION_MARK_SIZE;
+        continuation_mark_colour,
+        "Continuation mark colour",
+        ValueType::Colour,
+        HELP_CONTINUATION_MARK_COLOUR;
+        continuation_mark_font_size_mm,
+        "Continuation mark font size (mm)",
+        ValueType::Number,
+        HELP_CONTINUATION_MARK_FONT_SIZE;
+    );
+
+    // Glue overlap
+    config_macro_add_item!(
+        "Glue overlap",
+        HELP_GLUE_GROUP;
+        glue_overlap_mm,
+        "Glue overlap (mm)",
+        ValueType::Number,
+        HELP_GLUE_OVERLAP;
+        glue_colour,
+        "Glue area colour",
+        ValueType::Colour,
+        HELP_GLUE_COLOUR;
+    );
+}
+
+// Defaults for settings files that were written before the G-code settings existed
+fn default_gcode_travel_feed() -> f64 {
+    3000f64
+}
+
+fn default_gcode_plunge_feed() -> f64 {
+    300f64
+}
+
+fn default_gcode_safe_z() -> f64 {
+    5f64
+}
+
+fn default_gcode_punch_z() -> f64 {
+    -1f64
+}
+
+// Defaults for settings files that were written before the cut line settings existed
+fn default_cut_line_enable() -> bool {
+    true
+}
+
+fn default_cut_line_distance() -> f64 {
+    6f64
+}
+
+fn default_cut_line_thickness() -> f64 {
+    0.1f64
+}
+
+fn default_cut_line_colour() -> String {
+    "#0000ff".to_string()
+}
+
+// Defaults for settings files that were written before the note label settings existed
+fn default_note_label_font_size() -> f64 {
+    1.8f64
+}
+
+fn default_note_label_colour() -> String {
+    "#000000".to_string()
+}
+
+// Defaults for settings files that were written before the bar line settings existed
+fn default_bar_line_thickness() -> f64 {
+    0.3f64
+}
+
+fn default_bar_line_colour() -> String {
+    "#000000".to_string()
+}
+
+fn default_beat_line_thickness() -> f64 {
+    0.1f64
+}
+
+fn default_beat_line_colour() -> String {
+    "#a0a0a0".to_string()
+}
+
+fn default_bar_number_font_size() -> f64 {
+    2f64
+}
+
+// Defaults for settings files that were written before the page header and continuation mark settings existed
+fn default_page_header_enable() -> bool {
+    true
+}
+
+fn default_page_header_font_size() -> f64 {
+    3f64
+}
+
+fn default_page_header_colour() -> String {
+    "#000000".to_string()
+}
+
+fn default_continuation_mark_enable() -> bool {
+    true
+}
+
+fn default_continuation_mark_size() -> f64 {
+    3f64
+}
+
+fn default_continuation_mark_colour() -> String {
+    "#ff00ff".to_string()
+}
+
+fn default_continuation_mark_font_size() -> f64 {
+    2f64
+}
+
+// Defaults for settings files that were written before the glue overlap settings existed
+fn default_glue_overlap() -> f64 {
+    10f64
+}
+
+fn default_glue_colour() -> String {
+    "#e0e0e0".to_string()
 }
 
 // Help
@@ -286,3 +758,97 @@ const HELP_SPROCKET_HOLE_DISTANCE: &str =
 const HELP_SPROCKET_HOLE_DISTANCE_STAFF: &str = r#"This is the vertical distance of the sprocket holes to the staff (first staff line on the bottom and top, the black ones)."#;
 
 const HELP_SPROCKET_HOLE_COLOUR: &str = r#"This is the colour of the sprocket holes."#;
+
+// Row stacking
+const HELP_ROW_STACKING_GROUP: &str =
+    r#"These are settings concerning stacking multiple strip rows on one page."#;
+
+const HELP_ROW_STACKING_ENABLE: &str = r#"This enables or disables row stacking. If enabled, a page is filled with as many strip rows as fit the paper height before a new file is started. The rows are read from top to bottom and chevrons at the ends of the rows mark where the strip continues."#;
+
+const HELP_ROW_GAP: &str =
+    r#"This is the vertical gap between the bounding boxes of two strip rows."#;
+
+// G-code
+const HELP_GCODE_GROUP: &str = r#"These are settings concerning the G-code output for plotters and drilling or punching machines. The coordinates are the same millimetres as in the svg file, so y grows downwards."#;
+
+const HELP_GCODE_TRAVEL_FEED: &str = r#"This is the feed rate of the rapid moves between two holes. Machines that always do rapid moves at full speed ignore it."#;
+
+const HELP_GCODE_PLUNGE_FEED: &str =
+    r#"This is the feed rate used for moving down into the paper."#;
+
+const HELP_GCODE_SAFE_Z: &str = r#"This is the height the tool moves at between two holes."#;
+
+const HELP_GCODE_PUNCH_Z: &str =
+    r#"This is the height the tool moves down to for punching or drilling a hole."#;
+
+const HELP_GCODE_ORIGIN_X: &str = r#"This is added to the x coordinate of every hole. Use it to move the page on the machine bed."#;
+
+const HELP_GCODE_ORIGIN_Y: &str = r#"This is added to the y coordinate of every hole. Use it to move the page on the machine bed."#;
+
+// Cut line
+const HELP_CUT_LINE_GROUP: &str = r#"These are settings concerning the cut line, the outline of the paper strip. It is the blue outline in the 'cut' layer of the svg file."#;
+
+const HELP_CUT_LINE_ENABLE: &str = r#"This enables or disables the cut line."#;
+
+const HELP_CUT_LINE_DISTANCE: &str = r#"This is the vertical distance of the cut line to the staff (first staff line on the bottom and top, the black ones). The sprocket holes have to fit between the staff and the cut line."#;
+
+const HELP_CUT_LINE_THICKNESS: &str = r#"This is the thickness of the cut line."#;
+
+const HELP_CUT_LINE_COLOUR: &str = r#"This is the colour of the cut line."#;
+
+// Note labels
+const HELP_NOTE_LABEL_GROUP: &str = r#"These are settings concerning the note labels, the note names at the left end of every staff line."#;
+
+const HELP_NOTE_LABEL_ENABLE: &str = r#"This enables or disables the note labels."#;
+
+const HELP_NOTE_LABEL_FONT_SIZE: &str = r#"This is the font size of the note labels. Keep it below the distance of two staff lines so the labels don't overlap."#;
+
+const HELP_NOTE_LABEL_COLOUR: &str = r#"This is the colour of the note labels."#;
+
+// Bar lines
+const HELP_BAR_LINE_GROUP: &str = r#"These are settings concerning the bar lines, beat lines and bar numbers. They are taken from the time signature of the midi file, or 4/4 if there is none. Files using SMPTE timecode have no beats, so nothing is drawn for them."#;
+
+const HELP_BAR_LINE_ENABLE: &str = r#"This enables or disables the bar lines. They are drawn across the staff at the start of every bar."#;
+
+const HELP_BAR_LINE_THICKNESS: &str = r#"This is the thickness of the bar lines."#;
+
+const HELP_BAR_LINE_COLOUR: &str = r#"This is the colour of the bar lines and the bar numbers."#;
+
+const HELP_BEAT_LINE_ENABLE: &str = r#"This enables or disables the beat lines. They are drawn across the staff at every beat that doesn't start a bar."#;
+
+const HELP_BEAT_LINE_THICKNESS: &str = r#"This is the thickness of the beat lines. Keep it thinner than the bar lines so they can be told apart."#;
+
+const HELP_BEAT_LINE_COLOUR: &str = r#"This is the colour of the beat lines. Keep it lighter than the bar lines so they can be told apart."#;
+
+const HELP_BAR_NUMBER_ENABLE: &str = r#"This enables or disables the bar numbers. They are printed above the strip at the start of every bar."#;
+
+const HELP_BAR_NUMBER_FONT_SIZE: &str = r#"This is the font size of the bar numbers."#;
+
+// Page header
+const HELP_PAGE_HEADER_GROUP: &str = r#"These are settings concerning the page header. It shows the title of the song, the name of the music box and the page number, e.g. "page 2 of 5". It is printed along the top edge of the page and the strip rows move down to make room for it."#;
+
+const HELP_PAGE_HEADER_ENABLE: &str = r#"This enables or disables the page header."#;
+
+const HELP_PAGE_HEADER_FONT_SIZE: &str = r#"This is the font size of the page header. The strip rows move down by the font size and the page margin to make room for it."#;
+
+const HELP_PAGE_HEADER_COLOUR: &str = r#"This is the colour of the page header."#;
+
+// Continuation marks
+const HELP_CONTINUATION_MARK_GROUP: &str = r#"These are settings concerning the continuation marks. A chevron is drawn after every strip row that is continued and another one in front of the row that continues it. Where the strip continues on another page, the number of that page is printed below the chevron."#;
+
+const HELP_CONTINUATION_MARK_ENABLE: &str = r#"This enables or disables the continuation marks."#;
+
+const HELP_CONTINUATION_MARK_SIZE: &str = r#"This is the size of the chevrons. Twice this much room is kept free after the end of every strip row."#;
+
+const HELP_CONTINUATION_MARK_COLOUR: &str =
+    r#"This is the colour of the chevrons and the page numbers next to them."#;
+
+const HELP_CONTINUATION_MARK_FONT_SIZE: &str =
+    r#"This is the font size of the page numbers next to the chevrons."#;
+
+// Glue overlap
+const HELP_GLUE_GROUP: &str = r#"These are settings concerning the glue overlap. The end of every strip row is repeated as a shaded glue area at the start of the row that continues it. The next row is glued under the end of the previous one, so that the alignment marks of both rows line up and the repeated holes lie on top of each other."#;
+
+const HELP_GLUE_OVERLAP: &str = r#"This is the length of the glue area. Zero disables it."#;
+
+const HELP_GLUE_COLOUR: &str = r#"This is the colour of the shaded glue area."#;
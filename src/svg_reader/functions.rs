@@ -0,0 +1,164 @@
+// Internal
+use super::{Element, SvgReader};
+use crate::prelude::*;
+
+impl SvgReader {
+    /// Parses the elements of an svg file. Text content, comments and declarations are skipped
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut elements = Vec::<Element>::new();
+        // The ids of the groups the parser is in. Outermost first
+        let mut groups = Vec::<Option<String>>::new();
+        let mut rest = text;
+
+        while let Some(start) = rest.find('<') {
+            rest = &rest[start..];
+
+            // Skip comments, declarations and processing instructions
+            if let Some(t) = rest.strip_prefix("<!--") {
+                rest = match t.find("-->") {
+                    Some(end) => &t[end + 3..],
+                    None => return Err(Error::Generic("Unclosed comment in svg".to_string())),
+                };
+                continue;
+            }
+            let end = match rest.find('>') {
+                Some(t) => t,
+                None => return Err(Error::Generic("Unclosed tag in svg".to_string())),
+            };
+            let tag = &rest[1..end];
+            rest = &rest[end + 1..];
+            if tag.starts_with('?') || tag.starts_with('!') {
+                continue;
+            }
+
+            // Closing tag
+            if let Some(t) = tag.strip_prefix('/') {
+                if t.trim() == "g" {
+                    groups.pop();
+                }
+                continue;
+            }
+
+            let self_closing = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let name_end = tag.find(|el: char| el.is_whitespace()).unwrap_or(tag.len());
+            let element = Element {
+                name: tag[..name_end].to_string(),
+                layer: groups.iter().flatten().next().cloned(),
+                attributes: Self::parse_attributes(&tag[name_end..])?,
+            };
+
+            if element.name == "g" && !self_closing {
+                groups.push(element.attribute("id").map(|el| el.to_string()));
+            }
+            elements.push(element);
+        }
+
+        Ok(Self { elements })
+    }
+
+    /// Parses the attributes of a tag, e.g. ` cx="10" cy="20"`
+    fn parse_attributes(text: &str) -> Result<Vec<(String, String)>> {
+        let mut attributes = Vec::<(String, String)>::new();
+        let mut rest = text.trim_start();
+
+        while !rest.is_empty() {
+            let equals = match rest.find('=') {
+                Some(t) => t,
+                None => {
+                    return Err(Error::Generic(format!(
+                        "Attribute without value in svg: '{rest}'"
+                    )))
+                }
+            };
+            let name = rest[..equals].trim().to_string();
+            rest = rest[equals + 1..].trim_start();
+
+            let quote = match rest.chars().next() {
+                Some(t) if t == '"' || t == '\'' => t,
+                _ => {
+                    return Err(Error::Generic(format!(
+                        "Unquoted attribute '{name}' in svg"
+                    )))
+                }
+            };
+            let end = match rest[1..].find(quote) {
+                Some(t) => t + 1,
+                None => {
+                    return Err(Error::Generic(format!(
+                        "Unclosed attribute '{name}' in svg"
+                    )))
+                }
+            };
+            attributes.push((name, unescape(&rest[1..end])));
+            rest = rest[end + 1..].trim_start();
+        }
+
+        Ok(attributes)
+    }
+
+    /// Returns the elements in the order they appear in the file
+    pub fn elements(&self) -> &Vec<Element> {
+        &self.elements
+    }
+}
+
+impl Element {
+    /// Returns the value of an attribute
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|el| el.0 == name)
+            .map(|el| el.1.as_str())
+    }
+
+    /// Returns the value of an attribute as a number. A unit suffix like "mm" is ignored
+    pub fn number(&self, name: &str) -> Option<f64> {
+        self.attribute(name)?
+            .trim()
+            .trim_end_matches(|el: char| el.is_ascii_alphabetic())
+            .parse::<f64>()
+            .ok()
+    }
+}
+
+/// Replaces the predefined xml entities. The opposite of [crate::svg_writer::xml::escape]
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SvgReader;
+
+    #[test]
+    fn parse() {
+        let svg = r##"<?xml version="1.0"?>
+<svg width="297mm" height="210mm">
+<!-- <circle cx="0" /> -->
+<g id="holes">
+<g transform="translate(1)"><circle cx="10mm" cy='20' fill="#ff0000" /></g>
+</g>
+<circle cx="5" cy="6" fill="a &amp; b" />
+<text x="1" y="2">1 &lt; 2</text>
+</svg>"##;
+        let reader = SvgReader::parse(svg).unwrap();
+        let circles = reader
+            .elements()
+            .iter()
+            .filter(|el| el.name == "circle")
+            .collect::<Vec<_>>();
+
+        assert_eq!(circles.len(), 2);
+        assert_eq!(circles[0].layer.as_deref(), Some("holes"));
+        assert_eq!(circles[0].number("cx"), Some(10f64));
+        assert_eq!(circles[0].number("cy"), Some(20f64));
+        assert_eq!(circles[1].layer, None);
+        assert_eq!(circles[1].attribute("fill"), Some("a & b"));
+        assert_eq!(reader.elements()[0].number("width"), Some(297f64));
+    }
+}
@@ -0,0 +1,20 @@
+// Modules
+pub mod functions;
+
+/// Reads the elements of an svg file. Only understands as much svg as the [crate::svg_writer] writes.
+#[derive(Debug, Default)]
+pub struct SvgReader {
+    /// The elements in the order they appear in the file
+    elements: Vec<Element>,
+}
+
+/// An element of an svg file
+#[derive(Debug, Clone)]
+pub struct Element {
+    /// The tag name, e.g. "circle"
+    pub name: String,
+    /// The id of the outermost group the element is in. This is the layer in files written by the [crate::svg_writer]
+    pub layer: Option<String>,
+    /// The attributes with the entities already replaced
+    attributes: Vec<(String, String)>,
+}
@@ -5,6 +5,9 @@ use std::fmt::Pointer;
 use crate::vec2::Vec2;
 
 use super::document::Child;
+use super::renderer::Renderer;
+use super::xml::escape;
+use crate::prelude::*;
 
 #[derive(Debug, Clone)]
 pub struct Circle<T> {
@@ -13,7 +16,7 @@ pub struct Circle<T> {
     fill: String,
 }
 
-impl<T: Clone + std::fmt::Display + 'static> Child for Circle<T> {
+impl<T: Clone + std::fmt::Display + Into<f64> + 'static> Child for Circle<T> {
     fn clone_dyn(&self) -> Box<dyn Child> {
         Box::new(self.clone())
     }
@@ -26,11 +29,19 @@ impl<T: Clone + std::fmt::Display + 'static> Child for Circle<T> {
         let centre_x = &self.centre.x;
         let centre_y = &self.centre.y;
         let radius = &self.radius;
-        let fill = &self.fill;
+        let fill = escape(&self.fill);
         format!(
             r#"<circle cx="{centre_x}{unit_suffix}" cy="{centre_y}{unit_suffix}" r="{radius}{unit_suffix}" fill="{fill}" />"#
         )
     }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> Result<()> {
+        renderer.circle(
+            Vec2::new(self.centre.x.clone().into(), self.centre.y.clone().into()),
+            self.radius.clone().into(),
+            &self.fill,
+        )
+    }
 }
 
 impl<T> Circle<T> {
@@ -1,15 +1,74 @@
+use super::group::Group;
+use super::renderer::Renderer;
 use crate::prelude::*;
+use crate::vec2::Vec2;
 use std::{fmt::Debug, fs::File, io::Write};
 
 pub trait Child {
     fn clone_dyn(&self) -> Box<dyn Child>;
     fn fmt_dyn(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
     fn print(&self, unit_suffix: String) -> String;
+    fn render(&self, renderer: &mut dyn Renderer) -> Result<()>;
+}
+
+/// What a child of a document is. Every layer is written as its own group so it can be hidden. Also lets other formats than svg treat holes differently from decorations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layer {
+    /// The shaded glue areas
+    Glue,
+    /// The note lines
+    Staff,
+    /// The bounding box around the staff
+    Score,
+    /// The bar and beat lines
+    Grid,
+    /// Everything else, e.g. continuation marks
+    Decoration,
+    /// The note names
+    Labels,
+    /// The sprocket holes
+    Sprockets,
+    /// The note holes
+    Holes,
+    /// The outline of the paper strip
+    Cut,
+}
+
+impl Layer {
+    /// All layers in the order they are drawn
+    pub const ALL: [Layer; 9] = [
+        Layer::Glue,
+        Layer::Staff,
+        Layer::Grid,
+        Layer::Score,
+        Layer::Decoration,
+        Layer::Labels,
+        Layer::Sprockets,
+        Layer::Holes,
+        Layer::Cut,
+    ];
+
+    /// Returns the id of the group of the layer
+    pub fn id(&self) -> &'static str {
+        match self {
+            Layer::Glue => "glue",
+            Layer::Staff => "staff",
+            Layer::Score => "score",
+            Layer::Grid => "grid",
+            Layer::Decoration => "decorations",
+            Layer::Labels => "labels",
+            Layer::Sprockets => "sprockets",
+            Layer::Holes => "holes",
+            Layer::Cut => "cut",
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct Document {
-    children: Vec<Box<dyn Child>>,
+    children: Vec<(Layer, Box<dyn Child>)>,
+    /// The physical page size. The children are positioned in the same unit
+    size: Vec2<f64>,
     unix_suffix: String,
 }
 
@@ -26,29 +85,70 @@ impl Debug for Box<dyn Child> {
 }
 
 impl Default for Document {
-    /// Default unit is millimetres
+    /// Default unit is millimetres and the default page size is A4 landscape
     fn default() -> Self {
         Self {
             children: Default::default(),
+            size: Vec2::new(297f64, 210f64),
             unix_suffix: "mm".to_string(),
         }
     }
 }
 
 impl Document {
-    pub fn append(&mut self, child: Box<dyn Child>) -> &mut Self {
-        self.children.push(child);
+    /// Creates an empty document with the given page size in millimetres
+    pub fn new(width: f64, height: f64) -> Self {
+        Self {
+            size: Vec2::new(width, height),
+            ..Default::default()
+        }
+    }
+
+    /// Appends a child to the given layer
+    pub fn append(&mut self, layer: Layer, child: Box<dyn Child>) -> &mut Self {
+        self.children.push((layer, child));
         self
     }
 
+    /// Returns the physical page size
+    pub fn size(&self) -> &Vec2<f64> {
+        &self.size
+    }
+
+    /// Draws all children with the given renderer. Layer by layer in the same order as the svg groups
+    pub fn render(&self, renderer: &mut dyn Renderer) -> Result<()> {
+        for layer in Layer::ALL {
+            renderer.set_layer(layer);
+            for (_, element) in self.children.iter().filter(|el| el.0 == layer) {
+                element.render(renderer)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn print(&self) -> String {
-        let start = r#"<svg version="1.1" xmlns="http://www.w3.org/2000/svg">"#.to_string();
+        let width = &self.size.x;
+        let height = &self.size.y;
+        let unit_suffix = &self.unix_suffix;
+        // The viewBox maps one user unit to one unit of the page size so the children are printed without a unit
+        let start = format!(
+            r#"<svg version="1.1" xmlns="http://www.w3.org/2000/svg" width="{width}{unit_suffix}" height="{height}{unit_suffix}" viewBox="0 0 {width} {height}">"#
+        );
         let end = r#"</svg>"#;
         let mut content = String::default();
 
-        for element in &self.children {
-            content += &element.print(self.unix_suffix.clone());
-            content += "\n"
+        for layer in Layer::ALL {
+            let mut group = Group::new_builder().set_id(layer.id().to_string());
+            let mut empty = true;
+            for (_, element) in self.children.iter().filter(|el| el.0 == layer) {
+                group = group.append(element.clone());
+                empty = false;
+            }
+
+            if !empty {
+                content += &group.finish().print(String::new());
+                content += "\n";
+            }
         }
 
         format!("{start}\n{content}{end}")
@@ -78,3 +178,56 @@ impl Document {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg_writer::{circle::Circle, path::Path};
+
+    #[test]
+    fn page_size() {
+        let document = Document::new(100f64, 50.5f64);
+        assert_eq!(
+            document.print(),
+            r#"<svg version="1.1" xmlns="http://www.w3.org/2000/svg" width="100mm" height="50.5mm" viewBox="0 0 100 50.5">
+</svg>"#
+        );
+    }
+
+    #[test]
+    fn layers() {
+        let mut document = Document::new(100f64, 50f64);
+        document.append(
+            Layer::Holes,
+            Circle::new_builder()
+                .set_centre(20f64, 20f64)
+                .set_radius(1f64)
+                .set_fill("#ff0000".to_string())
+                .finish(),
+        );
+        document.append(
+            Layer::Cut,
+            Path::new_builder()
+                .move_to(10f64, 10f64)
+                .line_to(90f64, 10f64)
+                .line_to(90f64, 30f64)
+                .line_to(10f64, 30f64)
+                .close()
+                .set_stroke("#0000ff".to_string())
+                .set_stroke_width(0.1f64)
+                .finish(),
+        );
+
+        // Layers are written in drawing order and empty layers are left out
+        let out = document.print();
+        assert_eq!(out.matches("<g id=").count(), 2);
+        assert!(out.contains(
+            r##"<g id="holes">
+<circle cx="20" cy="20" r="1" fill="#ff0000" />
+</g>
+<g id="cut">
+<path d="M 10 10 L 90 10 L 90 30 L 10 30 Z" fill="none" stroke="#0000ff" stroke-width="0.1" />
+</g>"##
+        ));
+    }
+}
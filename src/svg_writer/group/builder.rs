@@ -0,0 +1,37 @@
+use super::super::document::Child;
+use super::Group;
+
+pub struct GroupBuilder {
+    group: Group,
+}
+
+impl Group {
+    pub fn new_builder() -> GroupBuilder {
+        GroupBuilder {
+            group: Group::default(),
+        }
+    }
+}
+
+impl GroupBuilder {
+    pub fn set_id(mut self, id: String) -> Self {
+        self.group.id = Some(id);
+        self
+    }
+    pub fn set_class(mut self, class: String) -> Self {
+        self.group.class = Some(class);
+        self
+    }
+    pub fn set_transform(mut self, transform: String) -> Self {
+        self.group.transform = Some(transform);
+        self
+    }
+    pub fn append(mut self, child: Box<dyn Child>) -> Self {
+        self.group.children.push(child);
+        self
+    }
+    /// Every option is optional
+    pub fn finish(self) -> Box<Group> {
+        Box::new(self.group)
+    }
+}
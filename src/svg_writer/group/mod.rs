@@ -0,0 +1,167 @@
+pub mod builder;
+
+use std::fmt::Pointer;
+
+use crate::vec2::Vec2;
+
+use super::document::Child;
+use super::renderer::{Renderer, Translate};
+use super::xml::escape;
+use crate::prelude::*;
+
+/// A group of children. Written as a 'g' element
+#[derive(Debug, Clone, Default)]
+pub struct Group {
+    id: Option<String>,
+    class: Option<String>,
+    /// An svg transform. Formats other than svg only support 'translate(x, y)'
+    transform: Option<String>,
+    children: Vec<Box<dyn Child>>,
+}
+
+impl Group {
+    /// Returns the offset of a 'translate(x, y)' transform. None if the transform is something else
+    fn translation(&self) -> Option<Vec2<f64>> {
+        let transform = match &self.transform {
+            Some(t) => t.trim(),
+            None => return Some(Vec2::new(0f64, 0f64)),
+        };
+
+        let values = transform
+            .strip_prefix("translate(")?
+            .strip_suffix(')')?
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|el| !el.is_empty())
+            .map(|el| el.parse::<f64>().ok())
+            .collect::<Option<Vec<f64>>>()?;
+
+        match values.len() {
+            1 => Some(Vec2::new(values[0], 0f64)),
+            2 => Some(Vec2::new(values[0], values[1])),
+            _ => None,
+        }
+    }
+}
+
+impl Child for Group {
+    fn clone_dyn(&self) -> Box<dyn Child> {
+        Box::new(self.clone())
+    }
+
+    fn fmt_dyn(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+
+    fn print(&self, unit_suffix: String) -> String {
+        let mut attributes = String::new();
+        for (name, value) in [
+            ("id", &self.id),
+            ("class", &self.class),
+            ("transform", &self.transform),
+        ] {
+            if let Some(t) = value {
+                attributes += &format!(r#" {name}="{}""#, escape(t));
+            }
+        }
+
+        let mut content = format!("<g{attributes}>\n");
+        for element in &self.children {
+            content += &element.print(unit_suffix.clone());
+            content += "\n";
+        }
+        content += "</g>";
+        content
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> Result<()> {
+        let offset = match self.translation() {
+            Some(t) => t,
+            None => {
+                return Err(Error::Generic(format!(
+                    "Can't draw the transform '{}'. Only 'translate(x, y)' is supported outside of svg files.",
+                    self.transform.as_deref().unwrap_or_default()
+                )))
+            }
+        };
+
+        let mut translate = Translate {
+            inner: renderer,
+            offset,
+        };
+        for element in &self.children {
+            element.render(&mut translate)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg_writer::{circle::Circle, path::PathCommand, rect::Rect, text::TextAnchor};
+
+    /// Records the centres of circles
+    struct Recorder(Vec<(f64, f64)>);
+
+    impl Renderer for Recorder {
+        fn line(&mut self, _: Vec2<f64>, _: Vec2<f64>, _: &str, _: f64) -> Result<()> {
+            Ok(())
+        }
+        fn circle(&mut self, centre: Vec2<f64>, _: f64, _: &str) -> Result<()> {
+            self.0.push((centre.x, centre.y));
+            Ok(())
+        }
+        fn text(&mut self, _: Vec2<f64>, _: &str, _: f64, _: &str, _: TextAnchor) -> Result<()> {
+            Ok(())
+        }
+        fn path(
+            &mut self,
+            _: &[PathCommand<f64>],
+            _: Option<&str>,
+            _: Option<&str>,
+            _: f64,
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn print_and_render() {
+        let group = Group::new_builder()
+            .set_id("a&b".to_string())
+            .set_transform("translate(10, 5)".to_string())
+            .append(
+                Circle::new_builder()
+                    .set_centre(1f64, 2f64)
+                    .set_radius(1f64)
+                    .set_fill("#ff0000".to_string())
+                    .finish(),
+            )
+            .append(
+                Rect::new_builder()
+                    .set_position(0f64, 0f64)
+                    .set_size(2f64, 3f64)
+                    .set_stroke("#000000".to_string())
+                    .set_stroke_width(0.5f64)
+                    .finish(),
+            )
+            .finish();
+
+        assert_eq!(
+            group.print(String::new()),
+            r##"<g id="a&amp;b" transform="translate(10, 5)">
+<circle cx="1" cy="2" r="1" fill="#ff0000" />
+<rect x="0" y="0" width="2" height="3" fill="none" stroke="#000000" stroke-width="0.5" />
+</g>"##
+        );
+
+        let mut recorder = Recorder(Vec::new());
+        group.render(&mut recorder).unwrap();
+        assert_eq!(recorder.0, vec![(11f64, 7f64)]);
+
+        let rotated = Group::new_builder()
+            .set_transform("rotate(90)".to_string())
+            .finish();
+        assert!(rotated.render(&mut recorder).is_err());
+    }
+}
@@ -5,6 +5,9 @@ use std::fmt::Pointer;
 use crate::vec2::Vec2;
 
 use super::document::Child;
+use super::renderer::Renderer;
+use super::xml::escape;
+use crate::prelude::*;
 
 #[derive(Debug, Clone)]
 pub struct Line<T> {
@@ -14,7 +17,7 @@ pub struct Line<T> {
     stroke_width: T,
 }
 
-impl<T: Clone + std::fmt::Display + 'static> Child for Line<T> {
+impl<T: Clone + std::fmt::Display + Into<f64> + 'static> Child for Line<T> {
     fn clone_dyn(&self) -> Box<dyn Child> {
         Box::new(self.clone())
     }
@@ -28,10 +31,19 @@ impl<T: Clone + std::fmt::Display + 'static> Child for Line<T> {
         let start_y = &self.start.y;
         let end_x = &self.end.x;
         let end_y = &self.end.y;
-        let stroke = &self.stroke;
+        let stroke = escape(&self.stroke);
         let stroke_width = &self.stroke_width;
         format!(
-            r#"<line x1="{start_x}{unit_suffix}" y1="{start_y}{unit_suffix}" x2="{end_x}{unit_suffix}" y2="{end_y}{unit_suffix}" stroke="{stroke}" stroke_width="{stroke_width}{unit_suffix}" />"#
+            r#"<line x1="{start_x}{unit_suffix}" y1="{start_y}{unit_suffix}" x2="{end_x}{unit_suffix}" y2="{end_y}{unit_suffix}" stroke="{stroke}" stroke-width="{stroke_width}{unit_suffix}" />"#
+        )
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> Result<()> {
+        renderer.line(
+            Vec2::new(self.start.x.clone().into(), self.start.y.clone().into()),
+            Vec2::new(self.end.x.clone().into(), self.end.y.clone().into()),
+            &self.stroke,
+            self.stroke_width.clone().into(),
         )
     }
 }
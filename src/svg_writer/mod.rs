@@ -1,3 +1,9 @@
 pub mod circle;
 pub mod document;
+pub mod group;
 pub mod line;
+pub mod path;
+pub mod rect;
+pub mod renderer;
+pub mod text;
+pub mod xml;
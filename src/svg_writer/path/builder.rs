@@ -0,0 +1,61 @@
+use crate::vec2::Vec2;
+
+use super::{Path, PathCommand};
+
+pub struct PathBuilder<T> {
+    commands: Vec<PathCommand<T>>,
+    fill: Option<String>,
+    stroke: Option<String>,
+    stroke_width: Option<T>,
+}
+
+impl<T> Path<T> {
+    pub fn new_builder() -> PathBuilder<T> {
+        PathBuilder {
+            commands: Vec::new(),
+            fill: Option::None,
+            stroke: Option::None,
+            stroke_width: Option::None,
+        }
+    }
+}
+
+impl<T> PathBuilder<T> {
+    pub fn move_to(mut self, x: T, y: T) -> Self {
+        self.commands.push(PathCommand::MoveTo(Vec2::new(x, y)));
+        self
+    }
+    pub fn line_to(mut self, x: T, y: T) -> Self {
+        self.commands.push(PathCommand::LineTo(Vec2::new(x, y)));
+        self
+    }
+    pub fn close(mut self) -> Self {
+        self.commands.push(PathCommand::Close);
+        self
+    }
+    pub fn set_fill(mut self, fill: String) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+    pub fn set_stroke(mut self, stroke: String) -> Self {
+        self.stroke = Some(stroke);
+        self
+    }
+    pub fn set_stroke_width(mut self, stroke_width: T) -> Self {
+        self.stroke_width = Some(stroke_width);
+        self
+    }
+    /// Panics if there are no commands or the first one isn't a move. Fill and stroke are optional
+    pub fn finish(mut self) -> Box<Path<T>> {
+        assert!(matches!(
+            self.commands.first(),
+            Some(PathCommand::MoveTo(_))
+        ));
+        Box::new(Path {
+            commands: self.commands,
+            fill: self.fill,
+            stroke: self.stroke,
+            stroke_width: self.stroke_width,
+        })
+    }
+}
@@ -0,0 +1,75 @@
+pub mod builder;
+
+use std::fmt::Pointer;
+
+use crate::vec2::Vec2;
+
+use super::document::Child;
+use super::rect::paint_attributes;
+use super::renderer::Renderer;
+use crate::prelude::*;
+
+/// A command of the 'd' attribute of a path
+#[derive(Debug, Clone)]
+pub enum PathCommand<T> {
+    MoveTo(Vec2<T>),
+    LineTo(Vec2<T>),
+    /// Draws a line back to the start of the current sub path
+    Close,
+}
+
+#[derive(Debug, Clone)]
+pub struct Path<T> {
+    commands: Vec<PathCommand<T>>,
+    fill: Option<String>,
+    stroke: Option<String>,
+    stroke_width: Option<T>,
+}
+
+impl<T: Clone + std::fmt::Display + Into<f64> + 'static> Child for Path<T> {
+    fn clone_dyn(&self) -> Box<dyn Child> {
+        Box::new(self.clone())
+    }
+
+    fn fmt_dyn(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt(f)
+    }
+
+    /// Path data has no units so the unit suffix is only used for the stroke width
+    fn print(&self, unit_suffix: String) -> String {
+        let d = self
+            .commands
+            .iter()
+            .map(|el| match el {
+                PathCommand::MoveTo(t) => format!("M {} {}", t.x, t.y),
+                PathCommand::LineTo(t) => format!("L {} {}", t.x, t.y),
+                PathCommand::Close => "Z".to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+        let paint = paint_attributes(&self.fill, &self.stroke, &self.stroke_width, &unit_suffix);
+        format!(r#"<path d="{d}"{paint} />"#)
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> Result<()> {
+        let commands = self
+            .commands
+            .iter()
+            .map(|el| match el {
+                PathCommand::MoveTo(t) => {
+                    PathCommand::MoveTo(Vec2::new(t.x.clone().into(), t.y.clone().into()))
+                }
+                PathCommand::LineTo(t) => {
+                    PathCommand::LineTo(Vec2::new(t.x.clone().into(), t.y.clone().into()))
+                }
+                PathCommand::Close => PathCommand::Close,
+            })
+            .collect::<Vec<PathCommand<f64>>>();
+        renderer.path(
+            &commands,
+            self.fill.as_deref(),
+            self.stroke.as_deref(),
+            self.stroke_width.clone().map(|t| t.into()).unwrap_or(0f64),
+        )
+    }
+}
@@ -0,0 +1,57 @@
+use crate::vec2::Vec2;
+
+use super::Rect;
+
+pub struct RectBuilder<T> {
+    position: Option<Vec2<T>>,
+    size: Option<Vec2<T>>,
+    fill: Option<String>,
+    stroke: Option<String>,
+    stroke_width: Option<T>,
+}
+
+impl<T> Rect<T> {
+    pub fn new_builder() -> RectBuilder<T> {
+        RectBuilder {
+            position: Option::None,
+            size: Option::None,
+            fill: Option::None,
+            stroke: Option::None,
+            stroke_width: Option::None,
+        }
+    }
+}
+
+impl<T> RectBuilder<T> {
+    /// Sets the top left corner
+    pub fn set_position(mut self, x: T, y: T) -> Self {
+        self.position = Some(Vec2::new(x, y));
+        self
+    }
+    pub fn set_size(mut self, width: T, height: T) -> Self {
+        self.size = Some(Vec2::new(width, height));
+        self
+    }
+    pub fn set_fill(mut self, fill: String) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+    pub fn set_stroke(mut self, stroke: String) -> Self {
+        self.stroke = Some(stroke);
+        self
+    }
+    pub fn set_stroke_width(mut self, stroke_width: T) -> Self {
+        self.stroke_width = Some(stroke_width);
+        self
+    }
+    /// Panics if the position or size isn't set. Fill and stroke are optional
+    pub fn finish(mut self) -> Box<Rect<T>> {
+        Box::new(Rect {
+            position: self.position.unwrap(),
+            size: self.size.unwrap(),
+            fill: self.fill,
+            stroke: self.stroke,
+            stroke_width: self.stroke_width,
+        })
+    }
+}
@@ -0,0 +1,70 @@
+pub mod builder;
+
+use std::fmt::Pointer;
+
+use crate::vec2::Vec2;
+
+use super::document::Child;
+use super::renderer::Renderer;
+use super::xml::escape;
+use crate::prelude::*;
+
+#[derive(Debug, Clone)]
+pub struct Rect<T> {
+    /// The top left corner
+    position: Vec2<T>,
+    size: Vec2<T>,
+    fill: Option<String>,
+    stroke: Option<String>,
+    stroke_width: Option<T>,
+}
+
+impl<T: Clone + std::fmt::Display + Into<f64> + 'static> Child for Rect<T> {
+    fn clone_dyn(&self) -> Box<dyn Child> {
+        Box::new(self.clone())
+    }
+
+    fn fmt_dyn(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt(f)
+    }
+
+    fn print(&self, unit_suffix: String) -> String {
+        let x = &self.position.x;
+        let y = &self.position.y;
+        let width = &self.size.x;
+        let height = &self.size.y;
+        let paint = paint_attributes(&self.fill, &self.stroke, &self.stroke_width, &unit_suffix);
+        format!(
+            r#"<rect x="{x}{unit_suffix}" y="{y}{unit_suffix}" width="{width}{unit_suffix}" height="{height}{unit_suffix}"{paint} />"#
+        )
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> Result<()> {
+        renderer.rect(
+            Vec2::new(
+                self.position.x.clone().into(),
+                self.position.y.clone().into(),
+            ),
+            Vec2::new(self.size.x.clone().into(), self.size.y.clone().into()),
+            self.fill.as_deref(),
+            self.stroke.as_deref(),
+            self.stroke_width.clone().map(|t| t.into()).unwrap_or(0f64),
+        )
+    }
+}
+
+/// Returns the fill, stroke and stroke-width attributes of a shape. A missing fill or stroke is written as 'none'
+pub(super) fn paint_attributes<T: std::fmt::Display>(
+    fill: &Option<String>,
+    stroke: &Option<String>,
+    stroke_width: &Option<T>,
+    unit_suffix: &str,
+) -> String {
+    let fill = escape(fill.as_deref().unwrap_or("none"));
+    let stroke = escape(stroke.as_deref().unwrap_or("none"));
+    let mut out = format!(r#" fill="{fill}" stroke="{stroke}""#);
+    if let Some(t) = stroke_width {
+        out += &format!(r#" stroke-width="{t}{unit_suffix}""#);
+    }
+    out
+}
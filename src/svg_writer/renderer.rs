@@ -0,0 +1,122 @@
+// Internal
+use super::document::Layer;
+use super::path::PathCommand;
+use super::text::TextAnchor;
+use crate::prelude::*;
+use crate::vec2::Vec2;
+
+/// Draws the children of a [super::document::Document] into another format. All positions and sizes are in the unit of the document
+pub trait Renderer {
+    fn line(
+        &mut self,
+        start: Vec2<f64>,
+        end: Vec2<f64>,
+        stroke: &str,
+        stroke_width: f64,
+    ) -> Result<()>;
+    fn circle(&mut self, centre: Vec2<f64>, radius: f64, fill: &str) -> Result<()>;
+    /// The position is the start, middle or end of the baseline depending on the anchor
+    fn text(
+        &mut self,
+        position: Vec2<f64>,
+        text: &str,
+        font_size: f64,
+        fill: &str,
+        anchor: TextAnchor,
+    ) -> Result<()>;
+    fn path(
+        &mut self,
+        commands: &[PathCommand<f64>],
+        fill: Option<&str>,
+        stroke: Option<&str>,
+        stroke_width: f64,
+    ) -> Result<()>;
+    /// Draws the rectangle as a closed path unless a renderer has something better
+    fn rect(
+        &mut self,
+        position: Vec2<f64>,
+        size: Vec2<f64>,
+        fill: Option<&str>,
+        stroke: Option<&str>,
+        stroke_width: f64,
+    ) -> Result<()> {
+        self.path(
+            &[
+                PathCommand::MoveTo(Vec2::new(position.x, position.y)),
+                PathCommand::LineTo(Vec2::new(position.x + size.x, position.y)),
+                PathCommand::LineTo(Vec2::new(position.x + size.x, position.y + size.y)),
+                PathCommand::LineTo(Vec2::new(position.x, position.y + size.y)),
+                PathCommand::Close,
+            ],
+            fill,
+            stroke,
+            stroke_width,
+        )
+    }
+    /// Called before each child with the layer it belongs to
+    fn set_layer(&mut self, layer: Layer) {}
+}
+
+/// Moves everything drawn by another renderer. Used for groups with a translate transform
+pub struct Translate<'a> {
+    pub inner: &'a mut dyn Renderer,
+    pub offset: Vec2<f64>,
+}
+
+impl Translate<'_> {
+    fn apply(&self, point: Vec2<f64>) -> Vec2<f64> {
+        Vec2::new(point.x + self.offset.x, point.y + self.offset.y)
+    }
+}
+
+impl Renderer for Translate<'_> {
+    fn line(
+        &mut self,
+        start: Vec2<f64>,
+        end: Vec2<f64>,
+        stroke: &str,
+        stroke_width: f64,
+    ) -> Result<()> {
+        let (start, end) = (self.apply(start), self.apply(end));
+        self.inner.line(start, end, stroke, stroke_width)
+    }
+
+    fn circle(&mut self, centre: Vec2<f64>, radius: f64, fill: &str) -> Result<()> {
+        let centre = self.apply(centre);
+        self.inner.circle(centre, radius, fill)
+    }
+
+    fn text(
+        &mut self,
+        position: Vec2<f64>,
+        text: &str,
+        font_size: f64,
+        fill: &str,
+        anchor: TextAnchor,
+    ) -> Result<()> {
+        let position = self.apply(position);
+        self.inner.text(position, text, font_size, fill, anchor)
+    }
+
+    fn path(
+        &mut self,
+        commands: &[PathCommand<f64>],
+        fill: Option<&str>,
+        stroke: Option<&str>,
+        stroke_width: f64,
+    ) -> Result<()> {
+        let commands = commands
+            .iter()
+            .map(|el| match el {
+                PathCommand::MoveTo(t) => PathCommand::MoveTo(self.apply(t.clone())),
+                PathCommand::LineTo(t) => PathCommand::LineTo(self.apply(t.clone())),
+                PathCommand::Close => PathCommand::Close,
+            })
+            .collect::<Vec<PathCommand<f64>>>();
+        self.inner.path(&commands, fill, stroke, stroke_width)
+    }
+
+    fn set_layer(&mut self, layer: Layer) {
+        self.inner.set_layer(layer);
+    }
+}
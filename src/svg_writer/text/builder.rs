@@ -0,0 +1,57 @@
+use crate::vec2::Vec2;
+
+use super::{Text, TextAnchor};
+
+pub struct TextBuilder<T> {
+    position: Option<Vec2<T>>,
+    text: Option<String>,
+    font_size: Option<T>,
+    fill: Option<String>,
+    anchor: TextAnchor,
+}
+
+impl<T> Text<T> {
+    pub fn new_builder() -> TextBuilder<T> {
+        TextBuilder {
+            position: Option::None,
+            text: Option::None,
+            font_size: Option::None,
+            fill: Option::None,
+            anchor: TextAnchor::default(),
+        }
+    }
+}
+
+impl<T> TextBuilder<T> {
+    pub fn set_position(mut self, x: T, y: T) -> Self {
+        self.position = Some(Vec2::new(x, y));
+        self
+    }
+    pub fn set_text(mut self, text: String) -> Self {
+        self.text = Some(text);
+        self
+    }
+    pub fn set_font_size(mut self, font_size: T) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+    pub fn set_fill(mut self, fill: String) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+    /// Defaults to [TextAnchor::Start]
+    pub fn set_anchor(mut self, anchor: TextAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+    /// Panics if an option isn't set
+    pub fn finish(mut self) -> Box<Text<T>> {
+        Box::new(Text {
+            position: self.position.unwrap(),
+            text: self.text.unwrap(),
+            font_size: self.font_size.unwrap(),
+            fill: self.fill.unwrap(),
+            anchor: self.anchor,
+        })
+    }
+}
@@ -0,0 +1,74 @@
+pub mod builder;
+
+use std::fmt::Pointer;
+
+use crate::vec2::Vec2;
+
+use super::document::Child;
+use super::renderer::Renderer;
+use super::xml::escape;
+use crate::prelude::*;
+
+/// Where the position of a text is. The start, the middle or the end of the baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAnchor {
+    #[default]
+    Start,
+    Middle,
+    End,
+}
+
+impl TextAnchor {
+    /// Returns the value of the svg 'text-anchor' attribute
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TextAnchor::Start => "start",
+            TextAnchor::Middle => "middle",
+            TextAnchor::End => "end",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Text<T> {
+    position: Vec2<T>,
+    text: String,
+    font_size: T,
+    fill: String,
+    anchor: TextAnchor,
+}
+
+impl<T: Clone + std::fmt::Display + Into<f64> + 'static> Child for Text<T> {
+    fn clone_dyn(&self) -> Box<dyn Child> {
+        Box::new(self.clone())
+    }
+
+    fn fmt_dyn(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt(f)
+    }
+
+    fn print(&self, unit_suffix: String) -> String {
+        let x = &self.position.x;
+        let y = &self.position.y;
+        let font_size = &self.font_size;
+        let fill = escape(&self.fill);
+        let anchor = self.anchor.as_str();
+        let text = escape(&self.text);
+        format!(
+            r#"<text x="{x}{unit_suffix}" y="{y}{unit_suffix}" font-size="{font_size}{unit_suffix}" font-family="sans-serif" fill="{fill}" text-anchor="{anchor}">{text}</text>"#
+        )
+    }
+
+    fn render(&self, renderer: &mut dyn Renderer) -> Result<()> {
+        renderer.text(
+            Vec2::new(
+                self.position.x.clone().into(),
+                self.position.y.clone().into(),
+            ),
+            &self.text,
+            self.font_size.clone().into(),
+            &self.fill,
+            self.anchor,
+        )
+    }
+}
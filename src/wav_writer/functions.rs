@@ -0,0 +1,175 @@
+// std
+use std::{fs::File, io::Write};
+
+// Internal
+use super::{Pluck, WavWriter};
+use crate::prelude::*;
+
+/// The frequency ratios of the first modes of a tine, which vibrates like a beam that is clamped at one end, and their amplitudes
+const PARTIALS: [(f64, f64); 3] = [(1f64, 1f64), (6.267f64, 0.25f64), (17.547f64, 0.08f64)];
+
+/// The time in seconds after which the fundamental of an A4 tine has decayed to about a third. Lower tines ring longer
+const DECAY_SECONDS: f64 = 1.2f64;
+
+/// The longest a tine rings in seconds if it isn't plucked again
+const RING_SECONDS: f64 = 4f64;
+
+/// The time in seconds it takes the pin to pluck the tine. Avoids clicks
+const ATTACK_SECONDS: f64 = 0.002f64;
+
+/// The time in seconds it takes a pin to damp a ringing tine before plucking it again
+const DAMP_SECONDS: f64 = 0.01f64;
+
+/// The loudest sample relative to the largest possible one
+const PEAK: f64 = 0.8f64;
+
+impl WavWriter {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            plucks: Vec::<Pluck>::new(),
+        }
+    }
+
+    /// Plucks the tine with the given fundamental frequency in hertz at the given time in seconds
+    pub fn pluck(&mut self, start: f64, frequency: f64) {
+        self.plucks.push(Pluck {
+            start: start.max(0f64),
+            frequency,
+        });
+    }
+
+    /// Returns the samples from -1 to 1
+    fn synthesize(&self) -> Vec<f64> {
+        let mut plucks = self.plucks.clone();
+        plucks.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+        let rate = self.sample_rate as f64;
+        let length = plucks
+            .iter()
+            .map(|el| el.start + RING_SECONDS)
+            .fold(0f64, f64::max);
+        let mut samples = vec![0f64; (length * rate).ceil() as usize];
+
+        for (i, pluck) in plucks.iter().enumerate() {
+            // A tine stops ringing when it is plucked again
+            let end = plucks[i + 1..]
+                .iter()
+                .find(|el| el.frequency == pluck.frequency)
+                .map_or(pluck.start + RING_SECONDS, |el| {
+                    el.start.min(pluck.start + RING_SECONDS)
+                });
+            let decay = DECAY_SECONDS * (440f64 / pluck.frequency).sqrt();
+
+            let first = (pluck.start * rate).round() as usize;
+            let last = ((end * rate).round() as usize).min(samples.len());
+            for (j, sample) in samples[first..last].iter_mut().enumerate() {
+                let t = j as f64 / rate;
+                let envelope = (t / ATTACK_SECONDS).min(1f64)
+                    * ((end - pluck.start - t) / DAMP_SECONDS).min(1f64);
+                for (ratio, amplitude) in PARTIALS {
+                    let frequency = pluck.frequency * ratio;
+                    // Partials above the nyquist frequency would alias
+                    if frequency >= rate / 2f64 {
+                        continue;
+                    }
+                    *sample += envelope
+                        * amplitude
+                        * (-t * ratio / decay).exp()
+                        * (2f64 * std::f64::consts::PI * frequency * t).sin();
+                }
+            }
+        }
+
+        // Normalize, so chords don't clip
+        let peak = samples.iter().fold(0f64, |acc, el| acc.max(el.abs()));
+        if peak > 0f64 {
+            samples.iter_mut().for_each(|el| *el *= PEAK / peak);
+        }
+        samples
+    }
+
+    /// Returns the wav file
+    pub fn print(&self) -> Vec<u8> {
+        let samples = self.synthesize();
+        let data_size = samples.len() as u32 * 2;
+
+        let mut output = Vec::<u8>::with_capacity(44 + data_size as usize);
+        output.extend_from_slice(b"RIFF");
+        output.extend_from_slice(&(36 + data_size).to_le_bytes());
+        output.extend_from_slice(b"WAVE");
+
+        // Uncompressed pcm, one channel, 16 bits per sample
+        output.extend_from_slice(b"fmt ");
+        output.extend_from_slice(&16u32.to_le_bytes());
+        output.extend_from_slice(&1u16.to_le_bytes());
+        output.extend_from_slice(&1u16.to_le_bytes());
+        output.extend_from_slice(&self.sample_rate.to_le_bytes());
+        output.extend_from_slice(&(self.sample_rate * 2).to_le_bytes());
+        output.extend_from_slice(&2u16.to_le_bytes());
+        output.extend_from_slice(&16u16.to_le_bytes());
+
+        output.extend_from_slice(b"data");
+        output.extend_from_slice(&data_size.to_le_bytes());
+        for sample in samples {
+            output.extend_from_slice(&((sample * i16::MAX as f64).round() as i16).to_le_bytes());
+        }
+        output
+    }
+
+    /// Writes the wav file
+    pub fn save(&self, path: &std::path::Path) -> Result<()> {
+        let mut file = match File::create(path) {
+            Ok(t) => t,
+            Err(e) => {
+                return Err(Error::IOError(
+                    Box::new(e),
+                    Box::new(path.to_string_lossy().to_string()),
+                ))
+            }
+        };
+
+        match file.write_all(&self.print()) {
+            Ok(t) => (),
+            Err(e) => {
+                return Err(Error::IOError(
+                    Box::new(e),
+                    Box::new(path.to_string_lossy().to_string()),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WavWriter;
+
+    #[test]
+    fn print() {
+        let mut writer = WavWriter::new(8000);
+        writer.pluck(0.5f64, 440f64);
+        writer.pluck(1f64, 440f64);
+        let wav = writer.print();
+
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 8000);
+        let data_size = u32::from_le_bytes(wav[40..44].try_into().unwrap()) as usize;
+        assert_eq!(wav.len(), 44 + data_size);
+
+        let samples = wav[44..]
+            .chunks(2)
+            .map(|el| i16::from_le_bytes([el[0], el[1]]))
+            .collect::<Vec<i16>>();
+        // Ends when the second pluck stops ringing
+        assert_eq!(samples.len(), 5 * 8000);
+        // Silent before the first pluck, ringing after it
+        assert!(samples[..4000].iter().all(|el| *el == 0));
+        assert!(samples[4000..4400].iter().any(|el| el.abs() > 1000));
+        // The second pluck damps the first one, so the tine is silent for an instant
+        assert_eq!(samples[8000], 0);
+    }
+}
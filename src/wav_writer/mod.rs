@@ -0,0 +1,21 @@
+// Modules
+pub mod functions;
+
+/// Synthesizes how a strip sounds on a music box and writes it into a 16 bit mono wav file.
+/// Every note plucks a tine of the comb. A tine rings until it decays or is plucked again.
+#[derive(Debug)]
+pub struct WavWriter {
+    /// The samples per second
+    sample_rate: u32,
+    /// The plucked tines in the order they were added
+    plucks: Vec<Pluck>,
+}
+
+/// A tine that is plucked at a time
+#[derive(Debug, Clone, Copy)]
+struct Pluck {
+    /// The time in seconds from the start of the file
+    start: f64,
+    /// The fundamental frequency of the tine in hertz
+    frequency: f64,
+}